@@ -327,6 +327,66 @@ impl NonAbsentCommitVotes {
         }
     }
 
+    /// Verifies signatures of the votes needed to reach the threshold of given
+    /// tally in a single batch.
+    ///
+    /// Validators are visited in the same order as in
+    /// [`voting_power_in_impl`] and collection stops as soon as the tally
+    /// would reach its trust threshold, so no more signatures are checked
+    /// than the one-by-one path would check.  If the batch verifies, the
+    /// votes are marked as verified and [`Self::has_voted`] won’t check them
+    /// again.  Otherwise nothing is marked and `has_voted` identifies the
+    /// invalid signature.
+    pub fn verify_batch<V: signature::Verifier>(
+        &mut self,
+        validator_set: &ValidatorSet,
+        mut power: VotingPowerTally,
+        verifier: &V,
+    ) {
+        let mut pending = Vec::new();
+        for validator in validator_set.validators() {
+            if let Ok(idx) = self
+                .votes
+                .binary_search_by_key(&validator.address, NonAbsentCommitVote::validator_id)
+            {
+                let vote = &self.votes[idx];
+                if !vote.verified {
+                    let mut sign_bytes = Vec::with_capacity(Self::SIGN_BYTES_INITIAL_CAPACITY);
+                    vote.signed_vote
+                        .sign_bytes_into(&mut sign_bytes)
+                        .expect("buffer is resized if needed and encoding never fails");
+                    pending.push((idx, validator.pub_key, sign_bytes));
+                }
+
+                power.tally(validator.power());
+                if power.check().is_ok() {
+                    break;
+                }
+            }
+        }
+
+        // A batch of one is no faster than verifying the signature directly.
+        if pending.len() < 2 {
+            return;
+        }
+
+        let batch = pending
+            .iter()
+            .map(|(idx, pub_key, sign_bytes)| {
+                (
+                    *pub_key,
+                    sign_bytes.as_slice(),
+                    self.votes[*idx].signed_vote.signature(),
+                )
+            })
+            .collect::<Vec<_>>();
+        if verifier.verify_batch(&batch).is_ok() {
+            for (idx, _, _) in pending {
+                self.votes[idx].verified = true;
+            }
+        }
+    }
+
     /// Looks up a vote cast by given validator.
     ///
     /// If the validator didn’t cast a vote or voted for `nil`, returns `Ok(None)`. Otherwise, if
@@ -429,6 +489,8 @@ fn voting_power_in_impl<V: signature::Verifier>(
     let mut power = VotingPowerTally::new(total_voting_power, trust_threshold);
     let mut seen_vals = Vec::new();
 
+    votes.verify_batch(validator_set, power, verifier);
+
     for validator in validator_set.validators() {
         if let Some(idx) = votes.has_voted(validator, verifier)? {
             // Check if this validator has already voted.
@@ -457,7 +519,12 @@ fn voting_power_in_impl<V: signature::Verifier>(
 // TODO: We plan to add Lightweight MBT for `voting_power_in` in the near future
 #[cfg(test)]
 mod tests {
-    use tendermint::trust_threshold::TrustThresholdFraction;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use tendermint::{
+        crypto::default::signature::Verifier as DefaultVerifier,
+        trust_threshold::TrustThresholdFraction,
+    };
     use tendermint_testgen::{
         light_block::generate_signed_header, Commit, Generator, Header,
         LightBlock as TestgenLightBlock, ValidatorSet, Vote as TestgenVote,
@@ -609,4 +676,85 @@ mod tests {
         // ensure the result matches the expected result
         assert_eq!(result_ok.unwrap(), EXPECTED_RESULT);
     }
+
+    /// Signature verifier which counts how signatures are checked.
+    #[derive(Default)]
+    struct CountingVerifier {
+        single: AtomicUsize,
+        batches: AtomicUsize,
+    }
+
+    impl signature::Verifier for CountingVerifier {
+        fn verify(
+            &self,
+            pubkey: tendermint::PublicKey,
+            msg: &[u8],
+            signature: &tendermint::Signature,
+        ) -> Result<(), signature::Error> {
+            self.single.fetch_add(1, Ordering::SeqCst);
+            DefaultVerifier.verify(pubkey, msg, signature)
+        }
+
+        fn verify_batch(
+            &self,
+            batch: &[(tendermint::PublicKey, &[u8], &tendermint::Signature)],
+        ) -> Result<(), signature::Error> {
+            self.batches.fetch_add(1, Ordering::SeqCst);
+            DefaultVerifier.verify_batch(batch)
+        }
+    }
+
+    #[test]
+    fn test_batch_verification() {
+        let vp_calculator = ProvidedVotingPowerCalculator::new(CountingVerifier::default());
+
+        let light_block: LightBlock = TestgenLightBlock::new_default(10)
+            .generate()
+            .unwrap()
+            .into();
+
+        let tally = vp_calculator
+            .voting_power_in(
+                &light_block.signed_header,
+                &light_block.validators,
+                TrustThreshold::TWO_THIRDS,
+            )
+            .unwrap();
+
+        assert_eq!(tally.tallied, 100);
+        assert_eq!(vp_calculator.verifier.batches.load(Ordering::SeqCst), 1);
+        assert_eq!(vp_calculator.verifier.single.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_batch_verification_falls_back_on_invalid_signature() {
+        let vp_calculator = ProvidedVotingPowerCalculator::new(CountingVerifier::default());
+
+        let mut testgen_lb = TestgenLightBlock::new_default(10);
+        let mut commit = testgen_lb.commit.clone().unwrap();
+        let mut votes = commit.votes.unwrap();
+        let vote = votes.pop().unwrap();
+        let bad_validator = vote.validator.clone().unwrap().generate().unwrap();
+        let header = vote.clone().header.unwrap().chain_id("bad-chain");
+        votes.push(vote.header(header));
+
+        commit.votes = Some(votes);
+        testgen_lb.commit = Some(commit);
+        let light_block: LightBlock = testgen_lb.generate().unwrap().into();
+
+        let result_err = vp_calculator.voting_power_in(
+            &light_block.signed_header,
+            &light_block.validators,
+            TrustThreshold::TWO_THIRDS,
+        );
+
+        match result_err {
+            Err(VerificationError(VerificationErrorDetail::InvalidSignature(e), _)) => {
+                assert_eq!(e.validator.address, bad_validator.address);
+            },
+            _ => panic!("expected InvalidSignature error"),
+        }
+        assert_eq!(vp_calculator.verifier.batches.load(Ordering::SeqCst), 1);
+        assert!(vp_calculator.verifier.single.load(Ordering::SeqCst) > 0);
+    }
 }
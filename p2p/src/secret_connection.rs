@@ -21,6 +21,7 @@ use curve25519_dalek_ng::{
 use merlin::Transcript;
 use rand_core::OsRng;
 use subtle::ConstantTimeEq;
+use tendermint::crypto::signature::Signer;
 use tendermint_proto::v0_38 as proto;
 use tendermint_std_ext::TryClone;
 
@@ -60,8 +61,11 @@ pub struct Handshake<S> {
 /// Handshake states
 
 /// `AwaitingEphKey` means we're waiting for the remote ephemeral pubkey.
-pub struct AwaitingEphKey {
-    local_privkey: ed25519_consensus::SigningKey,
+///
+/// The local key may be any [`Signer`] producing Ed25519 signatures, e.g. one
+/// backed by an HSM or a KMS.
+pub struct AwaitingEphKey<S = ed25519_consensus::SigningKey> {
+    local_privkey: S,
     local_eph_privkey: Option<EphemeralSecret>,
 }
 
@@ -75,13 +79,10 @@ pub struct AwaitingAuthSig {
 }
 
 #[allow(clippy::use_self)]
-impl Handshake<AwaitingEphKey> {
+impl<S: Signer> Handshake<AwaitingEphKey<S>> {
     /// Initiate a handshake.
     #[must_use]
-    pub fn new(
-        local_privkey: ed25519_consensus::SigningKey,
-        protocol_version: Version,
-    ) -> (Self, EphemeralPublic) {
+    pub fn new(local_privkey: S, protocol_version: Version) -> (Self, EphemeralPublic) {
         // Generate an ephemeral key for perfect forward secrecy.
         let local_eph_privkey = EphemeralSecret::random(&mut OsRng);
         let local_eph_pubkey = X25519_BASEPOINT * local_eph_privkey;
@@ -155,7 +156,10 @@ impl Handshake<AwaitingEphKey> {
             self.state.local_privkey.sign(&sc_mac)
         } else {
             self.state.local_privkey.sign(&kdf.challenge)
-        };
+        }
+        .map_err(|_| Error::signature())?;
+        let local_signature = ed25519_consensus::Signature::try_from(local_signature.as_bytes())
+            .map_err(|_| Error::signature())?;
 
         Ok(Handshake {
             protocol_version: self.protocol_version,
@@ -190,7 +194,8 @@ impl Handshake<AwaitingAuthSig> {
                 ed25519_consensus::VerificationKey::try_from(&bytes[..])
                     .map_err(|_| Error::signature())
             },
-            proto::crypto::public_key::Sum::Secp256k1(_) => Err(Error::unsupported_key()),
+            proto::crypto::public_key::Sum::Secp256k1(_)
+            | proto::crypto::public_key::Sum::Bls12381(_) => Err(Error::unsupported_key()),
         }?;
 
         let remote_sig = ed25519_consensus::Signature::try_from(auth_sig_msg.sig.as_slice())
@@ -279,16 +284,22 @@ impl<IoHandler: Read + Write + Send + Sync> SecretConnection<IoHandler> {
     ///
     /// # Errors
     ///
+    /// * if the local key is not an Ed25519 key
     /// * if sharing of the pubkey fails
     /// * if sharing of the signature fails
     /// * if receiving the signature fails
-    pub fn new(
+    pub fn new<S: Signer>(
         mut io_handler: IoHandler,
-        local_privkey: ed25519_consensus::SigningKey,
+        local_privkey: S,
         protocol_version: Version,
     ) -> Result<Self, Error> {
         // Start a handshake process.
-        let local_pubkey = PublicKey::from(&local_privkey);
+        let local_pubkey = match local_privkey.public_key() {
+            tendermint::PublicKey::Ed25519(pk) => {
+                PublicKey::from_raw_ed25519(pk.as_bytes()).map_err(|_| Error::invalid_key())?
+            },
+            _ => return Err(Error::unsupported_key()),
+        };
         let (mut h, local_eph_pubkey) = Handshake::new(local_privkey, protocol_version);
 
         // Write local ephemeral pubkey and receive one too.
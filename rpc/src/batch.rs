@@ -0,0 +1,264 @@
+//! JSON-RPC batch requests
+
+use alloc::collections::BTreeMap;
+use core::marker::PhantomData;
+
+use crate::dialect::{v0_34, v0_37, v0_38};
+use crate::request::{RequestMessage, Wrapper as RequestWrapper};
+use crate::response::Wrapper as ResponseWrapper;
+use crate::{client::CompatMode, prelude::*, Error, Id, SimpleRequest};
+
+/// A batch of JSON-RPC requests, sent to the node in a single round trip.
+///
+/// The requests in a batch may be of different types. Adding a request to the
+/// batch returns a [`BatchItem`], which is used to retrieve the corresponding
+/// response from the [`BatchResponse`].
+///
+/// The responses are parsed according to the compatibility mode of the client
+/// that performed the batch, so a batch can be sent to a node of any
+/// supported version.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{batch::Batch, endpoint, HttpClient};
+///
+/// let mut batch = Batch::new();
+/// let block = batch.add(endpoint::block::Request::new(10u32.into()));
+/// let commit = batch.add(endpoint::commit::Request::new(10u32.into()));
+///
+/// let mut responses = client.perform_batch(batch).await?;
+/// let block = responses.take(block)?;
+/// let commit = responses.take(commit)?;
+/// ```
+#[derive(Default)]
+pub struct Batch {
+    requests: Vec<String>,
+}
+
+impl Batch {
+    /// Create an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a request to the batch.
+    ///
+    /// Each request is given a random ID, by which its response is matched.
+    pub fn add<R>(&mut self, request: R) -> BatchItem<R>
+    where
+        R: RequestMessage,
+    {
+        let wrapper = RequestWrapper::new(request);
+        let id = wrapper.id().clone();
+        self.requests.push(wrapper.into_json());
+        BatchItem {
+            id,
+            _request: PhantomData,
+        }
+    }
+
+    /// The number of requests in the batch.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Serialize the batch as a JSON array of requests.
+    pub fn into_json(self) -> String {
+        format!("[{}]", self.requests.join(","))
+    }
+}
+
+/// Refers to a request that was added to a [`Batch`].
+pub struct BatchItem<R> {
+    id: Id,
+    _request: PhantomData<fn() -> R>,
+}
+
+impl<R> BatchItem<R> {
+    /// The JSON-RPC ID of the request.
+    pub fn id(&self) -> &Id {
+        &self.id
+    }
+}
+
+/// The responses to a [`Batch`] of requests, indexed by request ID.
+#[derive(Debug, Default)]
+pub struct BatchResponse {
+    responses: BTreeMap<Id, serde_json::Value>,
+    compat: CompatMode,
+}
+
+impl BatchResponse {
+    /// Parse the responses to a batch from a JSON string, received from a
+    /// node that uses the given compatibility mode.
+    ///
+    /// If the node rejected the batch as a whole, it replies with a single
+    /// error response instead of an array, which is returned as an error.
+    pub fn from_string(response: impl AsRef<[u8]>, compat: CompatMode) -> Result<Self, Error> {
+        let value: serde_json::Value =
+            serde_json::from_slice(response.as_ref()).map_err(Error::serde)?;
+        let serde_json::Value::Array(values) = value else {
+            let wrapper: ResponseWrapper<serde_json::Value> =
+                serde_json::from_value(value).map_err(Error::serde)?;
+            return Err(wrapper.into_error().unwrap_or_else(Error::malformed_json));
+        };
+
+        let mut responses = BTreeMap::new();
+        for value in values {
+            let id = value
+                .get("id")
+                .cloned()
+                .ok_or_else(Error::malformed_json)
+                .and_then(|id| serde_json::from_value(id).map_err(Error::serde))?;
+            responses.insert(id, value);
+        }
+        Ok(Self { responses, compat })
+    }
+
+    /// The number of responses that have not been taken yet.
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Take the response to the given request out of the batch.
+    ///
+    /// Fails if the node did not respond to the request, or if it responded
+    /// with an error.
+    pub fn take<R, O>(&mut self, item: BatchItem<R>) -> Result<O, Error>
+    where
+        R: SimpleRequest<v0_34::Dialect, Output = O>
+            + SimpleRequest<v0_37::Dialect, Output = O>
+            + SimpleRequest<v0_38::Dialect, Output = O>,
+    {
+        let value = self
+            .responses
+            .remove(&item.id)
+            .ok_or_else(Error::mismatch_response)?;
+        match self.compat {
+            CompatMode::V0_38 => parse::<R, v0_38::Dialect>(value),
+            CompatMode::V0_37 => parse::<R, v0_37::Dialect>(value),
+            CompatMode::V0_34 => parse::<R, v0_34::Dialect>(value),
+        }
+    }
+}
+
+fn parse<R, S>(value: serde_json::Value) -> Result<R::Output, Error>
+where
+    R: SimpleRequest<S>,
+    S: crate::dialect::Dialect,
+{
+    let wrapper: ResponseWrapper<<R as crate::Request<S>>::Response> =
+        serde_json::from_value(value).map_err(Error::serde)?;
+    wrapper.into_result().map(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoint::{block_results, health, net_info};
+    use crate::error::ErrorDetail;
+
+    #[test]
+    fn batch_serializes_as_array() {
+        let mut batch = Batch::new();
+        let health = batch.add(health::Request);
+        let net_info = batch.add(net_info::Request);
+        assert_eq!(batch.len(), 2);
+
+        let json: serde_json::Value = serde_json::from_str(&batch.into_json()).unwrap();
+        let requests = json.as_array().unwrap();
+        assert_eq!(requests[0]["method"], "health");
+        assert_eq!(requests[1]["method"], "net_info");
+        assert_eq!(
+            serde_json::from_value::<Id>(requests[1]["id"].clone()).unwrap(),
+            *net_info.id()
+        );
+        assert_ne!(health.id(), net_info.id());
+    }
+
+    #[test]
+    fn responses_are_matched_by_id() {
+        let mut batch = Batch::new();
+        let health = batch.add(health::Request);
+        let net_info = batch.add(net_info::Request);
+        let missing = batch.add(health::Request);
+
+        // Responses may arrive in any order.
+        let body = serde_json::json!([
+            {
+                "jsonrpc": "2.0",
+                "id": net_info.id(),
+                "error": { "code": -32603, "message": "Internal error", "data": "oops" }
+            },
+            { "jsonrpc": "2.0", "id": health.id(), "result": {} }
+        ]);
+        let mut responses =
+            BatchResponse::from_string(body.to_string(), CompatMode::V0_38).unwrap();
+        assert_eq!(responses.len(), 2);
+
+        responses.take(health).unwrap();
+        let err = responses.take(net_info).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::Response(_)));
+        let err = responses.take(missing).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::MismatchResponse(_)));
+        assert!(responses.is_empty());
+    }
+
+    #[test]
+    fn responses_are_parsed_in_the_client_dialect() {
+        let mut batch = Batch::new();
+        let request = block_results::Request::new(10u32.into());
+        let v0_34 = batch.add(request.clone());
+        let v0_38 = batch.add(request);
+
+        // Event attributes are base64-encoded in v0.34 only.
+        let body = |id: &Id| {
+            serde_json::json!([{
+                "jsonrpc": "2.0",
+                "id": id,
+                "result": {
+                    "height": "10",
+                    "txs_results": null,
+                    "validator_updates": null,
+                    "consensus_param_updates": null,
+                    "begin_block_events": [{
+                        "type": "transfer",
+                        "attributes": [{ "key": "YW1vdW50", "value": "MTA=", "index": true }]
+                    }]
+                }
+            }])
+            .to_string()
+        };
+        let key = |response: block_results::Response| {
+            let events = response.begin_block_events.unwrap();
+            events[0].attributes[0].key_bytes().to_vec()
+        };
+
+        let mut responses =
+            BatchResponse::from_string(body(v0_34.id()), CompatMode::V0_34).unwrap();
+        assert_eq!(key(responses.take(v0_34).unwrap()), b"amount");
+        let mut responses =
+            BatchResponse::from_string(body(v0_38.id()), CompatMode::V0_38).unwrap();
+        assert_eq!(key(responses.take(v0_38).unwrap()), b"YW1vdW50");
+    }
+
+    #[test]
+    fn rejected_batch_is_an_error() {
+        let body = r#"{
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": { "code": -32600, "message": "Invalid Request", "data": "empty batch" }
+        }"#;
+        let err = BatchResponse::from_string(body, CompatMode::V0_38).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::Response(_)));
+    }
+}
@@ -30,6 +30,17 @@ pub mod sync;
 ))]
 mod transport;
 
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub mod cache;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use cache::CachingClient;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub mod middleware;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use middleware::MiddlewareClient;
+
+#[cfg(feature = "http-client")]
+pub use transport::failover::{self, FailoverClient};
 #[cfg(feature = "http-client")]
 pub use transport::http::{self, HttpClient, HttpClientUrl};
 #[cfg(feature = "websocket-client")]
@@ -189,6 +200,15 @@ pub trait Client {
         self.perform(broadcast::tx_commit::Request::new(tx)).await
     }
 
+    /// `/check_tx`: check a transaction with the application, without
+    /// adding it to the mempool.
+    async fn check_tx<T>(&self, tx: T) -> Result<check_tx::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        self.perform(check_tx::Request::new(tx)).await
+    }
+
     /// `/commit`: get block commit at a given height.
     async fn commit<H>(&self, height: H) -> Result<commit::Response, Error>
     where
@@ -212,6 +232,12 @@ pub trait Client {
         self.perform(consensus_state::Request::new()).await
     }
 
+    /// `/dump_consensus_state`: get the complete consensus state, including
+    /// the round state of every peer
+    async fn dump_consensus_state(&self) -> Result<dump_consensus_state::Response, Error> {
+        self.perform(dump_consensus_state::Request::new()).await
+    }
+
     // TODO(thane): Simplify once validators endpoint removes pagination.
     /// `/validators`: get validators a given height.
     async fn validators<H>(&self, height: H, paging: Paging) -> Result<validators::Response, Error>
@@ -320,6 +346,12 @@ pub trait Client {
         self.perform(net_info::Request).await
     }
 
+    /// `/num_unconfirmed_txs`: get the number of unconfirmed transactions in
+    /// the mempool.
+    async fn num_unconfirmed_txs(&self) -> Result<num_unconfirmed_txs::Response, Error> {
+        self.perform(num_unconfirmed_txs::Request).await
+    }
+
     /// `/status`: get Tendermint status including node info, pubkey, latest
     /// block hash, app hash, block height and time.
     async fn status(&self) -> Result<status::Response, Error> {
@@ -349,6 +381,50 @@ pub trait Client {
             .await
     }
 
+    /// `/unconfirmed_txs`: get at most `limit` unconfirmed transactions from
+    /// the mempool.
+    ///
+    /// If `limit` is not specified, the node returns at most
+    /// [`unconfirmed_txs::DEFAULT_UNCONFIRMED_TXS_LIMIT`] transactions.
+    async fn unconfirmed_txs(
+        &self,
+        limit: Option<u32>,
+    ) -> Result<unconfirmed_txs::Response, Error> {
+        self.perform(unconfirmed_txs::Request::new(limit)).await
+    }
+
+    /// `/dial_seeds`: dial the given seed nodes (unsafe).
+    #[cfg(feature = "unsafe")]
+    async fn dial_seeds(&self, seeds: Vec<String>) -> Result<dial_seeds::Response, Error> {
+        self.perform(dial_seeds::Request::new(seeds)).await
+    }
+
+    /// `/dial_peers`: dial the given peers, optionally adding them to the
+    /// node's persistent, unconditional and/or private peers (unsafe).
+    #[cfg(feature = "unsafe")]
+    async fn dial_peers(
+        &self,
+        peers: Vec<String>,
+        persistent: bool,
+        unconditional: bool,
+        private: bool,
+    ) -> Result<dial_peers::Response, Error> {
+        self.perform(
+            dial_peers::Request::new(peers)
+                .persistent(persistent)
+                .unconditional(unconditional)
+                .private(private),
+        )
+        .await
+    }
+
+    /// `/unsafe_flush_mempool`: remove all transactions from the mempool
+    /// (unsafe).
+    #[cfg(feature = "unsafe")]
+    async fn unsafe_flush_mempool(&self) -> Result<unsafe_flush_mempool::Response, Error> {
+        self.perform(unsafe_flush_mempool::Request).await
+    }
+
     #[cfg(any(feature = "http-client", feature = "websocket-client"))]
     /// Poll the `/health` endpoint until it returns a successful result or
     /// the given `timeout` has elapsed.
@@ -6,14 +6,13 @@ use futures::StreamExt;
 use structopt::StructOpt;
 use tendermint::Hash;
 use tendermint_rpc::{
-    client::CompatMode,
     event::{self, Event, EventData},
     query::Query,
     Client, Error, HttpClient, Order, Paging, Scheme, Subscription, SubscriptionClient, Url,
     WebSocketClient,
 };
 use tokio::{task::JoinHandle, time::Duration};
-use tracing::{debug, error, info, level_filters::LevelFilter, warn};
+use tracing::{error, info, level_filters::LevelFilter, warn};
 
 /// CLI for performing simple interactions against a Tendermint node's RPC.
 ///
@@ -125,6 +124,12 @@ enum ClientRequest {
         /// The transaction to broadcast.
         tx: String,
     },
+    /// Check a transaction with the ABCI app, without adding it to the
+    /// mempool.
+    CheckTx {
+        /// The transaction to check.
+        tx: String,
+    },
     /// Get the commit for the given height.
     Commit { height: u32 },
     /// Get consensus parameters for a specific height.
@@ -134,6 +139,31 @@ enum ClientRequest {
     },
     /// Get the current consensus state.
     ConsensusState,
+    /// Dial the given peers (requires the node's unsafe RPC routes to be
+    /// enabled).
+    #[cfg(feature = "unsafe")]
+    DialPeers {
+        /// Peer addresses, in the form `<node ID>@<host>:<port>`.
+        peers: Vec<String>,
+        /// Add the peers to the node's persistent peers.
+        #[structopt(long)]
+        persistent: bool,
+        /// Add the peers to the node's unconditional peers.
+        #[structopt(long)]
+        unconditional: bool,
+        /// Add the peers to the node's private peers.
+        #[structopt(long)]
+        private: bool,
+    },
+    /// Dial the given seed nodes (requires the node's unsafe RPC routes to
+    /// be enabled).
+    #[cfg(feature = "unsafe")]
+    DialSeeds {
+        /// Seed addresses, in the form `<node ID>@<host>:<port>`.
+        seeds: Vec<String>,
+    },
+    /// Get the complete consensus state, including the state of all peers.
+    DumpConsensusState,
     /// Get the node's genesis data.
     Genesis,
     /// Get the node's genesis data by chunks
@@ -150,6 +180,8 @@ enum ClientRequest {
     LatestCommit,
     /// Obtain information about the P2P stack and other network connections.
     NetInfo,
+    /// Get the number of unconfirmed transactions in the mempool.
+    NumUnconfirmedTxs,
     /// Get Tendermint status (node info, public key, latest block hash, etc.).
     Status,
     /// Fetch a transaction by way of its hash.
@@ -175,6 +207,16 @@ enum ClientRequest {
         #[structopt(long)]
         prove: bool,
     },
+    /// Get unconfirmed transactions from the mempool.
+    UnconfirmedTxs {
+        /// The maximum number of transactions to retrieve.
+        #[structopt(long)]
+        limit: Option<u32>,
+    },
+    /// Remove all transactions from the mempool (requires the node's unsafe
+    /// RPC routes to be enabled).
+    #[cfg(feature = "unsafe")]
+    UnsafeFlushMempool,
     /// Get the validators at the given height.
     Validators {
         /// The height at which to query the validators.
@@ -254,24 +296,20 @@ fn get_http_proxy_url(url_scheme: Scheme, proxy_url: Option<Url>) -> Result<Opti
 }
 
 async fn http_request(url: Url, proxy_url: Option<Url>, req: Request) -> Result<(), Error> {
-    let mut client = match proxy_url {
+    let mut builder = HttpClient::builder(url.clone().try_into()?).detect_compat_mode();
+    match proxy_url {
         Some(proxy_url) => {
             info!(
                 "Using HTTP client with proxy {} to submit request to {}",
                 proxy_url, url
             );
-            HttpClient::new_with_proxy(url, proxy_url)
+            builder = builder.proxy_url(proxy_url.try_into()?);
         },
         None => {
             info!("Using HTTP client to submit request to: {}", url);
-            HttpClient::new(url)
         },
-    }?;
-
-    let status = client.status().await?;
-    let compat_mode = CompatMode::from_version(status.node_info.version)?;
-    debug!("Using compatibility mode {}", compat_mode);
-    client.set_compat_mode(compat_mode);
+    }
+    let client = builder.build()?;
 
     match req {
         Request::ClientRequest(r) => client_request(&client, r).await,
@@ -299,23 +337,12 @@ async fn websocket_request(url: Url, req: Request) -> Result<(), Error> {
 async fn start_websocket_client(
     url: Url,
 ) -> Result<(WebSocketClient, JoinHandle<Result<(), Error>>), Error> {
-    let (client, driver) = WebSocketClient::new(url.clone()).await?;
+    let (client, driver) = WebSocketClient::builder(url.try_into()?)
+        .detect_compat_mode()
+        .build()
+        .await?;
     let driver_hdl = tokio::spawn(async move { driver.run().await });
-    let status = client.status().await?;
-    let compat_mode = CompatMode::from_version(status.node_info.version)?;
-    if compat_mode == CompatMode::latest() {
-        debug!("Using compatibility mode {}", compat_mode);
-        Ok((client, driver_hdl))
-    } else {
-        debug!("Reconnecting with compatibility mode {}", compat_mode);
-        stop_websocket_client(client, driver_hdl).await?;
-        let (client, driver) = WebSocketClient::builder(url.try_into()?)
-            .compat_mode(compat_mode)
-            .build()
-            .await?;
-        let driver_hdl = tokio::spawn(async move { driver.run().await });
-        Ok((client, driver_hdl))
-    }
+    Ok((client, driver_hdl))
 }
 
 async fn stop_websocket_client(
@@ -387,6 +414,11 @@ where
             serde_json::to_string_pretty(&client.broadcast_tx_sync(tx).await?)
                 .map_err(Error::serde)?
         },
+        ClientRequest::CheckTx { tx } => {
+            // NOTE: this prints out the response in the 0.38+ format,
+            // regardless of the actual protocol version.
+            serde_json::to_string_pretty(&client.check_tx(tx).await?).map_err(Error::serde)?
+        },
         ClientRequest::ConsensusParams { height } => {
             serde_json::to_string_pretty(&client.consensus_params(height).await?)
                 .map_err(Error::serde)?
@@ -411,6 +443,26 @@ where
         ClientRequest::ConsensusState => {
             serde_json::to_string_pretty(&client.consensus_state().await?).map_err(Error::serde)?
         },
+        #[cfg(feature = "unsafe")]
+        ClientRequest::DialPeers {
+            peers,
+            persistent,
+            unconditional,
+            private,
+        } => serde_json::to_string_pretty(
+            &client
+                .dial_peers(peers, persistent, unconditional, private)
+                .await?,
+        )
+        .map_err(Error::serde)?,
+        #[cfg(feature = "unsafe")]
+        ClientRequest::DialSeeds { seeds } => {
+            serde_json::to_string_pretty(&client.dial_seeds(seeds).await?).map_err(Error::serde)?
+        },
+        ClientRequest::DumpConsensusState => {
+            serde_json::to_string_pretty(&client.dump_consensus_state().await?)
+                .map_err(Error::serde)?
+        },
         ClientRequest::Genesis => {
             serde_json::to_string_pretty(&client.genesis::<serde_json::Value>().await?)
                 .map_err(Error::serde)?
@@ -435,6 +487,10 @@ where
         ClientRequest::NetInfo => {
             serde_json::to_string_pretty(&client.net_info().await?).map_err(Error::serde)?
         },
+        ClientRequest::NumUnconfirmedTxs => {
+            serde_json::to_string_pretty(&client.num_unconfirmed_txs().await?)
+                .map_err(Error::serde)?
+        },
         ClientRequest::Status => {
             serde_json::to_string_pretty(&client.status().await?).map_err(Error::serde)?
         },
@@ -459,6 +515,15 @@ where
                 .await?,
         )
         .map_err(Error::serde)?,
+        ClientRequest::UnconfirmedTxs { limit } => {
+            serde_json::to_string_pretty(&client.unconfirmed_txs(limit).await?)
+                .map_err(Error::serde)?
+        },
+        #[cfg(feature = "unsafe")]
+        ClientRequest::UnsafeFlushMempool => {
+            serde_json::to_string_pretty(&client.unsafe_flush_mempool().await?)
+                .map_err(Error::serde)?
+        },
         ClientRequest::Validators {
             height,
             all,
@@ -0,0 +1,541 @@
+//! Caching of immutable, height-indexed RPC responses.
+
+use alloc::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use tendermint::{block::Height, chain, evidence::Evidence, Hash};
+
+use crate::client::Client;
+use crate::endpoint;
+use crate::paging::Paging;
+use crate::prelude::*;
+use crate::query::Query;
+use crate::{Error, Order, SimpleRequest};
+
+/// The default maximum number of responses held in memory.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// A Tendermint RPC client (implements [`crate::Client`]) that wraps another
+/// client, and caches the responses to requests for data at a fixed height,
+/// which never changes once the block at that height has been committed.
+///
+/// The responses to the following requests are cached when a height is
+/// given:
+///
+/// - `/block`
+/// - `/block_results`
+/// - `/commit` (only once the commit is canonical)
+/// - `/header`
+/// - `/validators`
+///
+/// All other requests, including those for the latest block, are passed
+/// through to the wrapped client.
+///
+/// Cached responses are held in a bounded in-memory cache, evicting the
+/// least recently used responses first. Optionally, responses can also be
+/// persisted to a directory on disk, which is consulted on a miss in memory,
+/// and may be shared with later instances of the client, including clients
+/// for other chains.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{Client, HttpClient};
+/// use tendermint_rpc::client::cache::CachingClient;
+///
+/// let client = CachingClient::builder(HttpClient::new("http://127.0.0.1:26657").unwrap())
+///     .capacity(10_000)
+///     .disk_cache("/var/cache/my-indexer/rpc", "cosmoshub-4".parse().unwrap())
+///     .build();
+///
+/// // Only the first call reaches the node.
+/// let block = client.block(10u32).await?;
+/// let block = client.block(10u32).await?;
+/// ```
+#[derive(Debug)]
+pub struct CachingClient<C> {
+    inner: C,
+    memory: Mutex<Lru>,
+    disk: Option<PathBuf>,
+}
+
+/// A builder for a [`CachingClient`].
+#[derive(Debug)]
+pub struct Builder<C> {
+    inner: C,
+    capacity: usize,
+    disk: Option<PathBuf>,
+}
+
+impl<C> Builder<C> {
+    /// Hold at most this many responses in memory.
+    ///
+    /// Defaults to [`DEFAULT_CAPACITY`].
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Also persist cached responses as files in the given directory, which
+    /// is created if it does not exist.
+    ///
+    /// The responses are stored in a subdirectory named after the ID of the
+    /// chain that the wrapped client connects to, so that the directory can
+    /// be shared between clients for different chains. The on-disk cache is
+    /// not bounded in size.
+    pub fn disk_cache(mut self, dir: impl Into<PathBuf>, chain_id: chain::Id) -> Self {
+        self.disk = Some(dir.into().join(chain_id.as_str()));
+        self
+    }
+
+    /// Create a client with the options specified for this builder.
+    pub fn build(self) -> CachingClient<C> {
+        CachingClient {
+            inner: self.inner,
+            memory: Mutex::new(Lru::new(self.capacity)),
+            disk: self.disk,
+        }
+    }
+}
+
+impl<C> CachingClient<C> {
+    /// Initiate a builder for a client wrapping `inner`.
+    pub fn builder(inner: C) -> Builder<C> {
+        Builder {
+            inner,
+            capacity: DEFAULT_CAPACITY,
+            disk: None,
+        }
+    }
+
+    /// The wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Remove all responses from the in-memory cache.
+    ///
+    /// The on-disk cache, if any, is left untouched.
+    pub fn clear(&self) {
+        self.memory.lock().unwrap().clear();
+    }
+
+    async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let cached = self.memory.lock().unwrap().get(key);
+        let json = match cached {
+            Some(json) => json,
+            None => {
+                let path = self.disk_path(key)?;
+                let json = blocking(move || std::fs::read_to_string(path)).await.ok()?;
+                self.memory.lock().unwrap().put(key, json.clone());
+                json
+            },
+        };
+        match serde_json::from_str(&json) {
+            Ok(response) => Some(response),
+            Err(e) => {
+                tracing::warn!("Ignoring malformed cached response for {}: {}", key, e);
+                None
+            },
+        }
+    }
+
+    async fn put<T: Serialize>(&self, key: &str, response: &T) {
+        let json = match serde_json::to_string(response) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!("Failed to serialize response for {}: {}", key, e);
+                return;
+            },
+        };
+        if let Some(path) = self.disk_path(key) {
+            let contents = json.clone();
+            if let Err(e) = blocking(move || write_file(&path, &contents)).await {
+                tracing::warn!("Failed to write cached response for {}: {}", key, e);
+            }
+        }
+        self.memory.lock().unwrap().put(key, json);
+    }
+
+    fn disk_path(&self, key: &str) -> Option<PathBuf> {
+        let file_name = format!("{}.json", key.replace('/', "_"));
+        self.disk.as_ref().map(|dir| dir.join(file_name))
+    }
+
+    /// Look up the response for `key`, or fetch it and cache it if
+    /// `cacheable` holds for it.
+    async fn cached<T, F>(
+        &self,
+        key: String,
+        fetch: F,
+        cacheable: fn(&T) -> bool,
+    ) -> Result<T, Error>
+    where
+        T: Serialize + DeserializeOwned,
+        F: core::future::Future<Output = Result<T, Error>>,
+    {
+        if let Some(response) = self.get(&key).await {
+            return Ok(response);
+        }
+        let response = fetch.await?;
+        if cacheable(&response) {
+            self.put(&key, &response).await;
+        }
+        Ok(response)
+    }
+}
+
+// Run file system operations off the async runtime's worker threads.
+async fn blocking<T, F>(f: F) -> std::io::Result<T>
+where
+    F: FnOnce() -> std::io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .unwrap_or_else(|e| Err(e.into()))
+}
+
+// Write to a temporary file first, so that a partially written response is
+// never read back.
+fn write_file(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+fn always<T>(_: &T) -> bool {
+    true
+}
+
+/// An in-memory cache of serialized responses, which evicts the least
+/// recently used response when full.
+#[derive(Debug)]
+struct Lru {
+    capacity: usize,
+    // Incremented on every access, to order the entries by recency
+    clock: u64,
+    entries: BTreeMap<String, (u64, String)>,
+    recency: BTreeMap<u64, String>,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            clock: 0,
+            entries: BTreeMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<String> {
+        self.clock += 1;
+        let (last_used, value) = self.entries.get_mut(key)?;
+        self.recency.remove(last_used);
+        self.recency.insert(self.clock, key.to_owned());
+        *last_used = self.clock;
+        Some(value.clone())
+    }
+
+    fn put(&mut self, key: &str, value: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        if let Some((last_used, _)) = self.entries.insert(key.to_owned(), (self.clock, value)) {
+            self.recency.remove(&last_used);
+        }
+        self.recency.insert(self.clock, key.to_owned());
+
+        while self.entries.len() > self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+#[async_trait]
+impl<C> Client for CachingClient<C>
+where
+    C: Client + Send + Sync,
+{
+    async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest,
+    {
+        self.inner.perform(request).await
+    }
+
+    async fn block<H>(&self, height: H) -> Result<endpoint::block::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.cached(format!("block/{height}"), self.inner.block(height), always)
+            .await
+    }
+
+    async fn block_results<H>(&self, height: H) -> Result<endpoint::block_results::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.cached(
+            format!("block_results/{height}"),
+            self.inner.block_results(height),
+            always,
+        )
+        .await
+    }
+
+    async fn commit<H>(&self, height: H) -> Result<endpoint::commit::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        // The commit for the latest height is not canonical, and will be
+        // superseded by the one included in the next block.
+        self.cached(
+            format!("commit/{height}"),
+            self.inner.commit(height),
+            |response: &endpoint::commit::Response| response.canonical,
+        )
+        .await
+    }
+
+    async fn header<H>(&self, height: H) -> Result<endpoint::header::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.cached(
+            format!("header/{height}"),
+            self.inner.header(height),
+            always,
+        )
+        .await
+    }
+
+    async fn validators<H>(
+        &self,
+        height: H,
+        paging: Paging,
+    ) -> Result<endpoint::validators::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        let key = match paging {
+            Paging::Default => format!("validators/{height}"),
+            Paging::All => format!("validators/{height}/all"),
+            Paging::Specific {
+                page_number,
+                per_page,
+            } => format!("validators/{height}/{page_number}/{per_page}"),
+        };
+        self.cached(key, self.inner.validators(height, paging), always)
+            .await
+    }
+
+    // The following requests are delegated to the corresponding methods of
+    // the wrapped client, which may take the RPC compatibility mode into
+    // account.
+
+    async fn block_by_hash(&self, hash: Hash) -> Result<endpoint::block_by_hash::Response, Error> {
+        self.inner.block_by_hash(hash).await
+    }
+
+    async fn latest_block(&self) -> Result<endpoint::block::Response, Error> {
+        self.inner.latest_block().await
+    }
+
+    async fn latest_block_results(&self) -> Result<endpoint::block_results::Response, Error> {
+        self.inner.latest_block_results().await
+    }
+
+    async fn block_search(
+        &self,
+        query: Query,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<endpoint::block_search::Response, Error> {
+        self.inner.block_search(query, page, per_page, order).await
+    }
+
+    async fn header_by_hash(
+        &self,
+        hash: Hash,
+    ) -> Result<endpoint::header_by_hash::Response, Error> {
+        self.inner.header_by_hash(hash).await
+    }
+
+    async fn broadcast_evidence(
+        &self,
+        evidence: Evidence,
+    ) -> Result<endpoint::evidence::Response, Error> {
+        self.inner.broadcast_evidence(evidence).await
+    }
+
+    async fn tx(&self, hash: Hash, prove: bool) -> Result<endpoint::tx::Response, Error> {
+        self.inner.tx(hash, prove).await
+    }
+
+    async fn tx_search(
+        &self,
+        query: Query,
+        prove: bool,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<endpoint::tx_search::Response, Error> {
+        self.inner
+            .tx_search(query, prove, page, per_page, order)
+            .await
+    }
+
+    async fn broadcast_tx_commit<T>(
+        &self,
+        tx: T,
+    ) -> Result<endpoint::broadcast::tx_commit::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        self.inner.broadcast_tx_commit(tx).await
+    }
+
+    async fn check_tx<T>(&self, tx: T) -> Result<endpoint::check_tx::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        self.inner.check_tx(tx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::response::Response;
+    use crate::Method;
+
+    /// Responds to `/block` and `/commit` requests with fixtures, counting
+    /// the requests it receives.
+    #[derive(Default)]
+    struct CountingClient {
+        requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Client for CountingClient {
+        async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+        where
+            R: SimpleRequest,
+        {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let fixture = match request.method() {
+                Method::Block => {
+                    include_str!(
+                        "../../tests/kvstore_fixtures/v0_38/incoming/block_at_height_10.json"
+                    )
+                },
+                Method::Commit => {
+                    include_str!(
+                        "../../tests/kvstore_fixtures/v0_38/incoming/commit_at_height_10.json"
+                    )
+                },
+                method => panic!("unexpected request: {method}"),
+            };
+            R::Response::from_string(fixture).map(Into::into)
+        }
+    }
+
+    fn requests(client: &CachingClient<CountingClient>) -> usize {
+        client.inner().requests.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn caches_height_pinned_requests_only() {
+        let client = CachingClient::builder(CountingClient::default()).build();
+
+        let first = client.block(10u32).await.unwrap();
+        let second = client.block(10u32).await.unwrap();
+        assert_eq!(first.block_id, second.block_id);
+        client.commit(10u32).await.unwrap();
+        client.commit(10u32).await.unwrap();
+        assert_eq!(requests(&client), 2);
+
+        client.latest_block().await.unwrap();
+        client.latest_block().await.unwrap();
+        client.latest_commit().await.unwrap();
+        assert_eq!(requests(&client), 5);
+
+        client.clear();
+        client.block(10u32).await.unwrap();
+        assert_eq!(requests(&client), 6);
+    }
+
+    #[tokio::test]
+    async fn reads_back_from_disk() {
+        let dir = std::env::temp_dir().join(format!("tendermint-rpc-cache-{}", std::process::id()));
+
+        let chain_id: chain::Id = "dockerchain".parse().unwrap();
+
+        let client = CachingClient::builder(CountingClient::default())
+            .disk_cache(&dir, chain_id.clone())
+            .build();
+        client.block(10u32).await.unwrap();
+
+        let client = CachingClient::builder(CountingClient::default())
+            .capacity(0)
+            .disk_cache(&dir, chain_id)
+            .build();
+        let block = client.block(10u32).await.unwrap();
+        assert_eq!(block.block.header.height.value(), 10);
+        assert_eq!(requests(&client), 0);
+
+        // Responses cached for another chain are not used.
+        let client = CachingClient::builder(CountingClient::default())
+            .capacity(0)
+            .disk_cache(&dir, "otherchain".parse().unwrap())
+            .build();
+        client.block(10u32).await.unwrap();
+        assert_eq!(requests(&client), 1);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut lru = Lru::new(2);
+        lru.put("a", "1".into());
+        lru.put("b", "2".into());
+        assert_eq!(lru.get("a").as_deref(), Some("1"));
+        lru.put("c", "3".into());
+        assert_eq!(lru.get("b"), None);
+        assert_eq!(lru.get("a").as_deref(), Some("1"));
+        assert_eq!(lru.get("c").as_deref(), Some("3"));
+        lru.put("a", "4".into());
+        assert_eq!(lru.entries.len(), 2);
+        assert_eq!(lru.get("a").as_deref(), Some("4"));
+    }
+}
@@ -0,0 +1,663 @@
+//! Retry, rate limiting and concurrency limiting for any [`Client`].
+
+use alloc::sync::Arc;
+use core::fmt;
+use core::future::Future;
+use core::time::Duration;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use tendermint::{block::Height, evidence::Evidence, Hash};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::client::Client;
+use crate::endpoint;
+use crate::error::ErrorDetail;
+use crate::prelude::*;
+use crate::query::Query;
+use crate::{Error, Method, Order, SimpleRequest};
+
+/// A Tendermint RPC client (implements [`crate::Client`]) that wraps another
+/// client, and retries failed requests, limits the rate at which requests are
+/// sent, and limits the number of requests in flight.
+///
+/// Each of these behaviors is opt-in, and configured through the
+/// [`Builder`]. Clones of a `MiddlewareClient` share the same rate and
+/// concurrency limits.
+///
+/// Requests that are not of a compatibility-sensitive kind are retried by
+/// round-tripping them through their JSON representation, since requests are
+/// not required to implement `Clone`.
+///
+/// Requests that broadcast a transaction or evidence are not idempotent, and
+/// are only retried if the retry policy allows it with
+/// [`RetryPolicy::retry_broadcasts`].
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use core::time::Duration;
+/// use tendermint_rpc::{Client, HttpClient};
+/// use tendermint_rpc::client::middleware::{MiddlewareClient, RateLimit, RetryPolicy};
+///
+/// let client = MiddlewareClient::builder(HttpClient::new("https://rpc.example.com").unwrap())
+///     .retry_policy(RetryPolicy::default().max_retries(5))
+///     .rate_limit(RateLimit::per_second(10).burst(20))
+///     .max_concurrency(4)
+///     .build();
+///
+/// let status = client.status().await?;
+/// ```
+#[derive(Debug)]
+pub struct MiddlewareClient<C> {
+    inner: C,
+    retry_policy: Option<RetryPolicy>,
+    rate_limit: Option<Arc<TokenBucket>>,
+    concurrency: Option<Arc<Semaphore>>,
+}
+
+impl<C: Clone> Clone for MiddlewareClient<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            retry_policy: self.retry_policy.clone(),
+            rate_limit: self.rate_limit.clone(),
+            concurrency: self.concurrency.clone(),
+        }
+    }
+}
+
+/// Determines which failed requests are retried, how often, and how long to
+/// wait between attempts.
+///
+/// The delay before the first retry is the initial backoff, and is doubled on
+/// every subsequent retry, up to the maximum backoff.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retry_broadcasts: bool,
+    retryable: Arc<dyn Fn(&Error) -> bool + Send + Sync>,
+}
+
+impl RetryPolicy {
+    /// Retry a failed request at most this many times.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Wait this long before the first retry.
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Never wait longer than this between retries.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Also retry the requests that broadcast a transaction or evidence.
+    ///
+    /// A request that failed with a transient error may still have reached
+    /// the node, in which case retrying it broadcasts the transaction or
+    /// evidence again. By default, these requests are sent only once.
+    pub fn retry_broadcasts(mut self, retry_broadcasts: bool) -> Self {
+        self.retry_broadcasts = retry_broadcasts;
+        self
+    }
+
+    /// Use the given predicate, instead of [`is_retryable`], to decide
+    /// whether a request that failed with an error should be retried.
+    pub fn retry_if<F>(mut self, retryable: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.retryable = Arc::new(retryable);
+        self
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .checked_mul(2u32.saturating_pow(attempt))
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Retries up to 3 times the errors classified as transient by
+    /// [`is_retryable`], backing off from 100ms up to 5s. Broadcasts are not
+    /// retried.
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            retry_broadcasts: false,
+            retryable: Arc::new(is_retryable),
+        }
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("retry_broadcasts", &self.retry_broadcasts)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Whether a request that failed with the given error may succeed if it is
+/// sent again.
+///
+/// Transport errors, timeouts reading from a WebSocket connection, and HTTP
+/// responses with status 429 (Too Many Requests) or 5xx are considered
+/// transient. Errors returned by the node in a JSON-RPC response are not.
+pub fn is_retryable(error: &Error) -> bool {
+    match error.detail() {
+        ErrorDetail::Http(_) | ErrorDetail::Io(_) | ErrorDetail::WebSocketTimeout(_) => true,
+        ErrorDetail::HttpRequestFailed(e) => {
+            let status = u16::from(e.status);
+            status == 429 || (500..600).contains(&status)
+        },
+        _ => false,
+    }
+}
+
+// Whether the method broadcasts a transaction or evidence, which is not
+// idempotent.
+fn is_broadcast(method: Method) -> bool {
+    matches!(
+        method,
+        Method::BroadcastTxAsync
+            | Method::BroadcastTxSync
+            | Method::BroadcastTxCommit
+            | Method::BroadcastEvidence
+    )
+}
+
+/// Limits the rate at which requests are sent, using a token bucket.
+///
+/// The bucket starts out full, allowing a burst of requests to be sent at
+/// once, and is refilled at the configured rate.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    requests: u32,
+    period: Duration,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// Allow at most `requests` requests per `period`, on average.
+    ///
+    /// The burst size defaults to `requests`.
+    pub fn new(requests: u32, period: Duration) -> Self {
+        Self {
+            requests,
+            period,
+            burst: requests,
+        }
+    }
+
+    /// Allow at most `requests` requests per second, on average.
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    /// Allow up to `burst` requests to be sent at once, after a period of
+    /// inactivity.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    // Tokens added per second
+    refill_rate: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        let capacity = f64::from(limit.burst.max(1));
+        Self {
+            capacity,
+            refill_rate: f64::from(limit.requests.max(1)) / limit.period.as_secs_f64(),
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Take a token, or return how long to wait for one to become available.
+    fn try_acquire(&self) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.refill_rate).min(self.capacity);
+        state.last_refill = now;
+
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - state.tokens) / self.refill_rate,
+            ))
+        }
+    }
+
+    async fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// A builder for a [`MiddlewareClient`].
+#[derive(Debug)]
+pub struct Builder<C> {
+    inner: C,
+    retry_policy: Option<RetryPolicy>,
+    rate_limit: Option<RateLimit>,
+    max_concurrency: Option<usize>,
+}
+
+impl<C> Builder<C> {
+    /// Retry failed requests according to the given policy.
+    ///
+    /// By default, failed requests are not retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Limit the rate at which requests, including retries, are sent.
+    ///
+    /// By default, the rate is not limited.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Allow at most this many requests to be in flight at once.
+    ///
+    /// By default, the number of requests in flight is not limited.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+
+    /// Create a client with the options specified for this builder.
+    pub fn build(self) -> MiddlewareClient<C> {
+        MiddlewareClient {
+            inner: self.inner,
+            retry_policy: self.retry_policy,
+            rate_limit: self
+                .rate_limit
+                .map(|limit| Arc::new(TokenBucket::new(limit))),
+            concurrency: self
+                .max_concurrency
+                .map(|max| Arc::new(Semaphore::new(max.max(1)))),
+        }
+    }
+}
+
+impl<C> MiddlewareClient<C> {
+    /// Initiate a builder for a client wrapping `inner`.
+    pub fn builder(inner: C) -> Builder<C> {
+        Builder {
+            inner,
+            retry_policy: None,
+            rate_limit: None,
+            max_concurrency: None,
+        }
+    }
+
+    /// The wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Wait until a request may be sent under the configured limits.
+    ///
+    /// The returned permit must be held until the request completes.
+    async fn admit(&self) -> Option<SemaphorePermit<'_>> {
+        let permit = match &self.concurrency {
+            // The semaphore is never closed
+            Some(semaphore) => Some(semaphore.acquire().await.unwrap()),
+            None => None,
+        };
+        if let Some(bucket) = &self.rate_limit {
+            bucket.acquire().await;
+        }
+        permit
+    }
+
+    /// Whether requests for the given method may be retried.
+    fn may_retry(&self, method: Method) -> bool {
+        match &self.retry_policy {
+            Some(policy) => policy.retry_broadcasts || !is_broadcast(method),
+            None => false,
+        }
+    }
+
+    /// Send a request without retrying it.
+    async fn call_once<T>(&self, request: impl Future<Output = T>) -> T {
+        let _permit = self.admit().await;
+        request.await
+    }
+
+    /// Send a request by calling `f`, retrying it as per the retry policy.
+    async fn call<T, F, Fut>(&self, mut f: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = {
+                let _permit = self.admit().await;
+                f().await
+            };
+            let backoff = match (result, &self.retry_policy) {
+                (Err(e), Some(policy))
+                    if attempt < policy.max_retries && (policy.retryable)(&e) =>
+                {
+                    let backoff = policy.backoff(attempt);
+                    tracing::debug!("Request failed ({}), retrying in {:?}", e, backoff);
+                    backoff
+                },
+                (result, _) => return result,
+            };
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl<C> Client for MiddlewareClient<C>
+where
+    C: Client + Send + Sync,
+{
+    async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest,
+    {
+        if !self.may_retry(request.method()) {
+            return self.call_once(self.inner.perform(request)).await;
+        }
+
+        let json = serde_json::to_string(&request).map_err(Error::serde)?;
+        let (inner, json) = (&self.inner, &json);
+        self.call(move || async move {
+            let request: R = serde_json::from_str(json).map_err(Error::serde)?;
+            inner.perform(request).await
+        })
+        .await
+    }
+
+    // The following requests are delegated to the corresponding methods of
+    // the wrapped client, which may take the RPC compatibility mode into
+    // account.
+
+    async fn block<H>(&self, height: H) -> Result<endpoint::block::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.call(|| self.inner.block(height)).await
+    }
+
+    async fn block_by_hash(&self, hash: Hash) -> Result<endpoint::block_by_hash::Response, Error> {
+        self.call(|| self.inner.block_by_hash(hash)).await
+    }
+
+    async fn latest_block(&self) -> Result<endpoint::block::Response, Error> {
+        self.call(|| self.inner.latest_block()).await
+    }
+
+    async fn block_results<H>(&self, height: H) -> Result<endpoint::block_results::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.call(|| self.inner.block_results(height)).await
+    }
+
+    async fn latest_block_results(&self) -> Result<endpoint::block_results::Response, Error> {
+        self.call(|| self.inner.latest_block_results()).await
+    }
+
+    async fn block_search(
+        &self,
+        query: Query,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<endpoint::block_search::Response, Error> {
+        self.call(|| {
+            self.inner
+                .block_search(query.clone(), page, per_page, order.clone())
+        })
+        .await
+    }
+
+    async fn header<H>(&self, height: H) -> Result<endpoint::header::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.call(|| self.inner.header(height)).await
+    }
+
+    async fn header_by_hash(
+        &self,
+        hash: Hash,
+    ) -> Result<endpoint::header_by_hash::Response, Error> {
+        self.call(|| self.inner.header_by_hash(hash)).await
+    }
+
+    async fn broadcast_evidence(
+        &self,
+        evidence: Evidence,
+    ) -> Result<endpoint::evidence::Response, Error> {
+        if !self.may_retry(Method::BroadcastEvidence) {
+            return self
+                .call_once(self.inner.broadcast_evidence(evidence))
+                .await;
+        }
+        self.call(|| self.inner.broadcast_evidence(evidence.clone()))
+            .await
+    }
+
+    async fn tx(&self, hash: Hash, prove: bool) -> Result<endpoint::tx::Response, Error> {
+        self.call(|| self.inner.tx(hash, prove)).await
+    }
+
+    async fn tx_search(
+        &self,
+        query: Query,
+        prove: bool,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<endpoint::tx_search::Response, Error> {
+        self.call(|| {
+            self.inner
+                .tx_search(query.clone(), prove, page, per_page, order.clone())
+        })
+        .await
+    }
+
+    async fn broadcast_tx_commit<T>(
+        &self,
+        tx: T,
+    ) -> Result<endpoint::broadcast::tx_commit::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        if !self.may_retry(Method::BroadcastTxCommit) {
+            return self.call_once(self.inner.broadcast_tx_commit(tx)).await;
+        }
+        let tx = tx.into();
+        self.call(|| self.inner.broadcast_tx_commit(tx.clone()))
+            .await
+    }
+
+    async fn check_tx<T>(&self, tx: T) -> Result<endpoint::check_tx::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        let tx = tx.into();
+        self.call(|| self.inner.check_tx(tx.clone())).await
+    }
+}
+
+#[cfg(all(test, feature = "http-client"))]
+mod tests {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::endpoint::health;
+    use crate::response::Response;
+
+    /// Fails the first `failures` requests with the given error, and responds
+    /// to `/health` requests after that.
+    struct FlakyClient {
+        failures: AtomicUsize,
+        attempts: AtomicUsize,
+        error: fn() -> Error,
+    }
+
+    impl FlakyClient {
+        fn new(failures: usize, error: fn() -> Error) -> Self {
+            Self {
+                failures: AtomicUsize::new(failures),
+                attempts: AtomicUsize::new(0),
+                error,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Client for FlakyClient {
+        async fn perform<R>(&self, _request: R) -> Result<R::Output, Error>
+        where
+            R: SimpleRequest,
+        {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            let failing = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failing {
+                return Err((self.error)());
+            }
+            R::Response::from_string(r#"{"jsonrpc":"2.0","id":"","result":{}}"#).map(Into::into)
+        }
+    }
+
+    fn too_many_requests() -> Error {
+        Error::http_request_failed(reqwest::StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    fn fast_retries() -> RetryPolicy {
+        RetryPolicy::default().initial_backoff(Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors() {
+        let client = MiddlewareClient::builder(FlakyClient::new(2, too_many_requests))
+            .retry_policy(fast_retries())
+            .build();
+        client.perform(health::Request).await.unwrap();
+        assert_eq!(client.inner().attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let client = MiddlewareClient::builder(FlakyClient::new(5, too_many_requests))
+            .retry_policy(fast_retries().max_retries(2))
+            .build();
+        let err = client.health().await.unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::HttpRequestFailed(_)));
+        assert_eq!(client.inner().attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_permanent_errors() {
+        let client = MiddlewareClient::builder(FlakyClient::new(1, || {
+            Error::http_request_failed(reqwest::StatusCode::NOT_FOUND)
+        }))
+        .retry_policy(fast_retries())
+        .build();
+        client.health().await.unwrap_err();
+        assert_eq!(client.inner().attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_broadcasts_by_default() {
+        let client = MiddlewareClient::builder(FlakyClient::new(1, too_many_requests))
+            .retry_policy(fast_retries())
+            .build();
+        client.broadcast_tx_sync(vec![1]).await.unwrap_err();
+        assert_eq!(client.inner().attempts.load(Ordering::SeqCst), 1);
+        client.broadcast_tx_commit(vec![1]).await.unwrap_err();
+        assert_eq!(client.inner().attempts.load(Ordering::SeqCst), 2);
+
+        let client = MiddlewareClient::builder(FlakyClient::new(2, too_many_requests))
+            .retry_policy(fast_retries().retry_broadcasts(true))
+            .build();
+        // The empty result of the last attempt is not a valid response.
+        let err = client.broadcast_tx_commit(vec![1]).await.unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::Serde(_)));
+        assert_eq!(client.inner().attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_delays_requests_beyond_burst() {
+        let client = MiddlewareClient::builder(FlakyClient::new(0, too_many_requests))
+            .rate_limit(RateLimit::per_second(20).burst(2))
+            .build();
+        let start = Instant::now();
+        for _ in 0..4 {
+            client.health().await.unwrap();
+        }
+        // The last two requests wait for a token each, at 50ms per token.
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let policy = RetryPolicy::default()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(500));
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(500));
+        assert_eq!(policy.backoff(40), Duration::from_millis(500));
+    }
+}
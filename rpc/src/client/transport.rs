@@ -4,9 +4,12 @@ mod auth;
 mod router;
 
 macro_rules! perform_with_compat {
-    ($self:expr, $request:expr) => {{
+    ($self:expr, $request:expr) => {
+        perform_with_compat!($self, $self.compat, $request)
+    };
+    ($self:expr, $compat:expr, $request:expr) => {{
         let request = $request;
-        match $self.compat {
+        match $compat {
             CompatMode::V0_38 => {
                 $self
                     .perform_with_dialect(request, crate::dialect::v0_38::Dialect)
@@ -26,6 +29,8 @@ macro_rules! perform_with_compat {
     }};
 }
 
+#[cfg(feature = "http-client")]
+pub mod failover;
 #[cfg(feature = "http-client")]
 pub mod http;
 #[cfg(feature = "mock-client")]
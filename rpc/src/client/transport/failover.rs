@@ -0,0 +1,643 @@
+//! RPC client that fails over between multiple HTTP endpoints.
+
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use tendermint::{block::Height, evidence::Evidence, Hash};
+
+use crate::client::{Client, CompatMode, HttpClient};
+use crate::dialect::{v0_34, v0_37, v0_38, Dialect, LatestDialect};
+use crate::endpoint;
+use crate::error::ErrorDetail;
+use crate::prelude::*;
+use crate::query::Query;
+use crate::{Error, Order, SimpleRequest};
+
+/// How a [`FailoverClient`] picks the endpoint to send a request to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Selection {
+    /// Always use the first available endpoint, in the order in which the
+    /// endpoints were given. The other endpoints are only used as fallbacks.
+    #[default]
+    Priority,
+    /// Spread requests across the available endpoints in turn.
+    RoundRobin,
+    /// Prefer the available endpoint with the lowest observed response time.
+    LowestLatency,
+}
+
+/// A Tendermint RPC client (implements [`crate::Client`]) that sends each
+/// request to one of several [`HttpClient`]s, failing over to the next one on
+/// transport errors and timeouts.
+///
+/// An endpoint is considered available unless the last request sent to it
+/// failed at the transport level, or the last health check found it to be
+/// unreachable, catching up, or lagging behind the other endpoints by more
+/// than the configured number of blocks. Unavailable endpoints are still
+/// tried, as a last resort, once the available ones have failed.
+///
+/// Health checks are performed on demand by calling
+/// [`FailoverClient::check_health`], typically from a periodic task.
+///
+/// Errors returned by the node itself (e.g. for an unknown height) are not
+/// retried against the other endpoints.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{Client, FailoverClient, HttpClient};
+/// use tendermint_rpc::client::failover::Selection;
+///
+/// let client = FailoverClient::builder(vec![
+///     HttpClient::new("http://node-0:26657").unwrap(),
+///     HttpClient::new("http://node-1:26657").unwrap(),
+/// ])
+/// .selection(Selection::RoundRobin)
+/// .max_height_lag(5)
+/// .build()
+/// .unwrap();
+///
+/// client.check_health().await;
+/// let status = client.status().await.unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FailoverClient {
+    endpoints: Arc<[Endpoint]>,
+    selection: Selection,
+    max_height_lag: Option<u64>,
+    next: Arc<AtomicUsize>,
+    compat: CompatMode,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    client: HttpClient,
+    state: Mutex<EndpointState>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct EndpointState {
+    unreachable: bool,
+    // Only set and cleared by health checks, as a successful request does
+    // not tell whether the node caught up.
+    catching_up: bool,
+    lagging: bool,
+    latency: Option<Duration>,
+    latest_block_height: Option<Height>,
+}
+
+impl EndpointState {
+    fn is_available(&self) -> bool {
+        !self.unreachable && !self.catching_up && !self.lagging
+    }
+
+    fn record_latency(&mut self, sample: Duration) {
+        // Exponentially weighted moving average, so that a single slow
+        // response does not disqualify an endpoint.
+        self.latency = Some(match self.latency {
+            Some(latency) => latency.mul_f64(0.8) + sample.mul_f64(0.2),
+            None => sample,
+        });
+    }
+}
+
+/// The builder pattern constructor for [`FailoverClient`].
+pub struct Builder {
+    clients: Vec<HttpClient>,
+    selection: Selection,
+    max_height_lag: Option<u64>,
+    compat: CompatMode,
+}
+
+impl Builder {
+    /// How to pick the endpoint to send each request to.
+    ///
+    /// The default is [`Selection::Priority`].
+    pub fn selection(mut self, selection: Selection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Consider an endpoint unavailable if, at the last health check, its
+    /// latest block height was more than `blocks` behind the highest one
+    /// reported by the endpoints.
+    ///
+    /// By default, lagging endpoints are not excluded.
+    pub fn max_height_lag(mut self, blocks: u64) -> Self {
+        self.max_height_lag = Some(blocks);
+        self
+    }
+
+    /// Use the specified compatibility mode for the Tendermint RPC protocol.
+    ///
+    /// This overrides the compatibility mode of the individual clients.
+    /// The default is the latest protocol version supported by this crate.
+    pub fn compat_mode(mut self, mode: CompatMode) -> Self {
+        self.compat = mode;
+        self
+    }
+
+    /// Try to create a client with the options specified for this builder.
+    pub fn build(self) -> Result<FailoverClient, Error> {
+        if self.clients.is_empty() {
+            return Err(Error::invalid_params(
+                "a failover client needs at least one endpoint".to_string(),
+            ));
+        }
+        let endpoints = self
+            .clients
+            .into_iter()
+            .map(|client| Endpoint {
+                client,
+                state: Mutex::new(EndpointState::default()),
+            })
+            .collect();
+
+        Ok(FailoverClient {
+            endpoints,
+            selection: self.selection,
+            max_height_lag: self.max_height_lag,
+            next: Arc::new(AtomicUsize::new(0)),
+            compat: self.compat,
+        })
+    }
+}
+
+impl FailoverClient {
+    /// Initiate a builder for a client that fails over between the given
+    /// clients.
+    pub fn builder(clients: Vec<HttpClient>) -> Builder {
+        Builder {
+            clients,
+            selection: Default::default(),
+            max_height_lag: None,
+            compat: Default::default(),
+        }
+    }
+
+    /// Query the `/status` endpoint of every node, and update their
+    /// availability accordingly.
+    pub async fn check_health(&self) {
+        for (i, endpoint) in self.endpoints.iter().enumerate() {
+            let start = Instant::now();
+            let result = endpoint.client.status().await;
+            let mut state = endpoint.state.lock().unwrap();
+            match result {
+                Ok(status) => {
+                    state.record_latency(start.elapsed());
+                    state.unreachable = false;
+                    state.catching_up = status.sync_info.catching_up;
+                    state.latest_block_height = Some(status.sync_info.latest_block_height);
+                },
+                Err(e) => {
+                    tracing::debug!("Health check failed for endpoint #{}: {}", i, e);
+                    state.unreachable = true;
+                    state.catching_up = false;
+                    state.latest_block_height = None;
+                },
+            }
+        }
+
+        let max_height = self
+            .endpoints
+            .iter()
+            .filter_map(|endpoint| endpoint.state().latest_block_height)
+            .max();
+        for endpoint in self.endpoints.iter() {
+            let mut state = endpoint.state.lock().unwrap();
+            state.lagging = match (self.max_height_lag, max_height, state.latest_block_height) {
+                (Some(max_lag), Some(max_height), Some(height)) => {
+                    height.value() + max_lag < max_height.value()
+                },
+                _ => false,
+            };
+        }
+    }
+
+    /// The order in which the endpoints should be tried for the next request.
+    fn endpoint_order(&self) -> Vec<usize> {
+        let states: Vec<EndpointState> = self.endpoints.iter().map(Endpoint::state).collect();
+        let mut order: Vec<usize> = (0..states.len()).collect();
+        match self.selection {
+            Selection::Priority => {},
+            Selection::RoundRobin => {
+                let next = self.next.fetch_add(1, Ordering::Relaxed);
+                order.rotate_left(next % states.len());
+            },
+            Selection::LowestLatency => {
+                // Endpoints without any measurement go last.
+                order.sort_by_key(|&i| (states[i].latency.is_none(), states[i].latency));
+            },
+        }
+        // Available endpoints first. The sort is stable, so the order chosen
+        // above is kept within each group.
+        order.sort_by_key(|&i| !states[i].is_available());
+        order
+    }
+
+    async fn perform_with_dialect<R, S>(&self, request: R, _dialect: S) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest<S>,
+        S: Dialect,
+    {
+        let request_body = request.into_json();
+        let mut last_error = None;
+        for i in self.endpoint_order() {
+            let endpoint = &self.endpoints[i];
+            let start = Instant::now();
+            match endpoint
+                .client
+                .perform_serialized::<R, S>(request_body.clone())
+                .await
+            {
+                Err(e) if is_transport_error(&e) => {
+                    tracing::debug!("Request to endpoint #{} failed, failing over: {}", i, e);
+                    endpoint.state.lock().unwrap().unreachable = true;
+                    last_error = Some(e);
+                },
+                result => {
+                    let mut state = endpoint.state.lock().unwrap();
+                    state.unreachable = false;
+                    state.record_latency(start.elapsed());
+                    return result;
+                },
+            }
+        }
+        Err(last_error.expect("failover client has at least one endpoint"))
+    }
+}
+
+impl Endpoint {
+    fn state(&self) -> EndpointState {
+        *self.state.lock().unwrap()
+    }
+}
+
+// Whether the request failed to reach the node or to get a response from it,
+// as opposed to the node responding with an error.
+fn is_transport_error(e: &Error) -> bool {
+    matches!(
+        e.detail(),
+        ErrorDetail::Http(_) | ErrorDetail::HttpRequestFailed(_)
+    )
+}
+
+#[async_trait]
+impl Client for FailoverClient {
+    async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest,
+    {
+        self.perform_with_dialect(request, LatestDialect).await
+    }
+
+    async fn block<H>(&self, height: H) -> Result<endpoint::block::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        perform_with_compat!(self, endpoint::block::Request::new(height.into()))
+    }
+
+    async fn block_by_hash(
+        &self,
+        hash: tendermint::Hash,
+    ) -> Result<endpoint::block_by_hash::Response, Error> {
+        perform_with_compat!(self, endpoint::block_by_hash::Request::new(hash))
+    }
+
+    async fn latest_block(&self) -> Result<endpoint::block::Response, Error> {
+        perform_with_compat!(self, endpoint::block::Request::default())
+    }
+
+    async fn block_results<H>(&self, height: H) -> Result<endpoint::block_results::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        perform_with_compat!(self, endpoint::block_results::Request::new(height.into()))
+    }
+
+    async fn latest_block_results(&self) -> Result<endpoint::block_results::Response, Error> {
+        perform_with_compat!(self, endpoint::block_results::Request::default())
+    }
+
+    async fn block_search(
+        &self,
+        query: Query,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<endpoint::block_search::Response, Error> {
+        perform_with_compat!(
+            self,
+            endpoint::block_search::Request::new(query, page, per_page, order)
+        )
+    }
+
+    async fn header<H>(&self, height: H) -> Result<endpoint::header::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        match self.compat {
+            CompatMode::V0_38 => {
+                self.perform_with_dialect(endpoint::header::Request::new(height), v0_38::Dialect)
+                    .await
+            },
+            CompatMode::V0_37 => {
+                self.perform_with_dialect(endpoint::header::Request::new(height), v0_37::Dialect)
+                    .await
+            },
+            CompatMode::V0_34 => {
+                // Back-fill with a request to /block endpoint and
+                // taking just the header from the response.
+                let resp = self
+                    .perform_with_dialect(endpoint::block::Request::new(height), v0_34::Dialect)
+                    .await?;
+                Ok(resp.into())
+            },
+        }
+    }
+
+    async fn header_by_hash(
+        &self,
+        hash: Hash,
+    ) -> Result<endpoint::header_by_hash::Response, Error> {
+        match self.compat {
+            CompatMode::V0_38 => {
+                self.perform_with_dialect(
+                    endpoint::header_by_hash::Request::new(hash),
+                    v0_38::Dialect,
+                )
+                .await
+            },
+            CompatMode::V0_37 => {
+                self.perform_with_dialect(
+                    endpoint::header_by_hash::Request::new(hash),
+                    v0_37::Dialect,
+                )
+                .await
+            },
+            CompatMode::V0_34 => {
+                // Back-fill with a request to /block_by_hash endpoint and
+                // taking just the header from the response.
+                let resp = self
+                    .perform_with_dialect(
+                        endpoint::block_by_hash::Request::new(hash),
+                        v0_34::Dialect,
+                    )
+                    .await?;
+                Ok(resp.into())
+            },
+        }
+    }
+
+    async fn broadcast_evidence(
+        &self,
+        evidence: Evidence,
+    ) -> Result<endpoint::evidence::Response, Error> {
+        match self.compat {
+            CompatMode::V0_38 => {
+                let request = endpoint::evidence::Request::new(evidence);
+                self.perform_with_dialect(request, v0_38::Dialect).await
+            },
+            CompatMode::V0_37 => {
+                let request = endpoint::evidence::Request::new(evidence);
+                self.perform_with_dialect(request, v0_37::Dialect).await
+            },
+            CompatMode::V0_34 => {
+                let request = endpoint::evidence::Request::new(evidence);
+                self.perform_with_dialect(request, v0_34::Dialect).await
+            },
+        }
+    }
+
+    async fn tx(&self, hash: Hash, prove: bool) -> Result<endpoint::tx::Response, Error> {
+        perform_with_compat!(self, endpoint::tx::Request::new(hash, prove))
+    }
+
+    async fn tx_search(
+        &self,
+        query: Query,
+        prove: bool,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<endpoint::tx_search::Response, Error> {
+        perform_with_compat!(
+            self,
+            endpoint::tx_search::Request::new(query, prove, page, per_page, order)
+        )
+    }
+
+    async fn broadcast_tx_commit<T>(
+        &self,
+        tx: T,
+    ) -> Result<endpoint::broadcast::tx_commit::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        perform_with_compat!(self, endpoint::broadcast::tx_commit::Request::new(tx))
+    }
+
+    async fn check_tx<T>(&self, tx: T) -> Result<endpoint::check_tx::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        perform_with_compat!(self, endpoint::check_tx::Request::new(tx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    // Serves JSON-RPC requests on a local port, responding to `/status` with
+    // the given result and to any other method with an empty result, and
+    // returns the URL to reach it.
+    async fn serve(status: serde_json::Value) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read until the end of the JSON body of the request.
+                while !request.ends_with(b"}") {
+                    stream.readable().await.unwrap();
+                    match stream.try_read(&mut buf) {
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => continue,
+                        Err(e) => panic!("{}", e),
+                    }
+                }
+                let request: serde_json::Value = {
+                    let body_start = request.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 4;
+                    serde_json::from_slice(&request[body_start..]).unwrap()
+                };
+                let result = match request["method"].as_str() {
+                    Some("status") => status.clone(),
+                    _ => serde_json::json!({}),
+                };
+                let body = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": result
+                })
+                .to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let mut response = response.as_bytes();
+                while !response.is_empty() {
+                    stream.writable().await.unwrap();
+                    match stream.try_write(response) {
+                        Ok(n) => response = &response[n..],
+                        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => continue,
+                        Err(e) => panic!("{}", e),
+                    }
+                }
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    // A `/status` result reporting the given sync state.
+    fn status(latest_block_height: u64, catching_up: bool) -> serde_json::Value {
+        let mut status: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../tests/kvstore_fixtures/v0_38/incoming/status.json"
+        ))
+        .unwrap();
+        let sync_info = &mut status["result"]["sync_info"];
+        sync_info["latest_block_height"] = latest_block_height.to_string().into();
+        sync_info["catching_up"] = catching_up.into();
+        status["result"].take()
+    }
+
+    // Returns the URL of a local port on which nothing is listening.
+    async fn unreachable_url() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        format!("http://{addr}")
+    }
+
+    fn client(urls: &[String], selection: Selection) -> FailoverClient {
+        let clients = urls
+            .iter()
+            .map(|url| HttpClient::new(url.as_str()).unwrap())
+            .collect();
+        FailoverClient::builder(clients)
+            .selection(selection)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn requires_an_endpoint() {
+        assert!(FailoverClient::builder(vec![]).build().is_err());
+    }
+
+    #[tokio::test]
+    async fn fails_over_on_transport_error() {
+        let urls = [unreachable_url().await, serve(status(1, false)).await];
+        let client = client(&urls, Selection::Priority);
+
+        client.health().await.unwrap();
+
+        // The unreachable endpoint is now tried last.
+        assert!(!client.endpoints[0].state().is_available());
+        assert!(client.endpoints[1].state().is_available());
+        assert_eq!(client.endpoint_order(), vec![1, 0]);
+    }
+
+    #[tokio::test]
+    async fn health_check() {
+        let urls = [
+            serve(status(100, true)).await,
+            serve(status(94, false)).await,
+            serve(status(100, false)).await,
+            unreachable_url().await,
+        ];
+        let clients = urls
+            .iter()
+            .map(|url| HttpClient::new(url.as_str()).unwrap())
+            .collect();
+        let client = FailoverClient::builder(clients)
+            .max_height_lag(5)
+            .build()
+            .unwrap();
+
+        client.check_health().await;
+        let states: Vec<_> = client.endpoints.iter().map(Endpoint::state).collect();
+        assert!(states[0].catching_up && !states[0].unreachable && !states[0].lagging);
+        assert!(states[1].lagging && !states[1].catching_up);
+        assert!(states[2].is_available());
+        assert!(states[3].unreachable && states[3].latest_block_height.is_none());
+        assert_eq!(client.endpoint_order(), vec![2, 0, 1, 3]);
+
+        // Answering a request does not mean that the node caught up.
+        let client = FailoverClient::builder(vec![HttpClient::new(urls[0].as_str()).unwrap()])
+            .build()
+            .unwrap();
+        client.check_health().await;
+        client.health().await.unwrap();
+        assert!(!client.endpoints[0].state().is_available());
+
+        // Within the allowed lag.
+        let clients = urls[1..3]
+            .iter()
+            .map(|url| HttpClient::new(url.as_str()).unwrap())
+            .collect();
+        let client = FailoverClient::builder(clients)
+            .max_height_lag(6)
+            .build()
+            .unwrap();
+        client.check_health().await;
+        assert!(client.endpoints[1].state().is_available());
+        assert!(client.endpoints[0].state().is_available());
+    }
+
+    #[tokio::test]
+    async fn all_endpoints_failing() {
+        let urls = [unreachable_url().await, unreachable_url().await];
+        let client = client(&urls, Selection::Priority);
+
+        let err = client.health().await.unwrap_err();
+        assert!(is_transport_error(&err));
+    }
+
+    #[tokio::test]
+    async fn round_robin_and_latency_order() {
+        let urls = [
+            unreachable_url().await,
+            unreachable_url().await,
+            unreachable_url().await,
+        ];
+        let client = client(&urls, Selection::RoundRobin);
+        assert_eq!(client.endpoint_order(), vec![0, 1, 2]);
+        assert_eq!(client.endpoint_order(), vec![1, 2, 0]);
+        client.endpoints[2].state.lock().unwrap().lagging = true;
+        assert_eq!(client.endpoint_order(), vec![0, 1, 2]);
+        assert_eq!(client.endpoint_order(), vec![0, 1, 2]);
+        assert_eq!(client.endpoint_order(), vec![1, 0, 2]);
+
+        let client = FailoverClient {
+            selection: Selection::LowestLatency,
+            ..client
+        };
+        client.endpoints[1].state.lock().unwrap().latency = Some(Duration::from_millis(50));
+        client.endpoints[2].state.lock().unwrap().latency = Some(Duration::from_millis(10));
+        client.endpoints[2].state.lock().unwrap().lagging = false;
+        assert_eq!(client.endpoint_order(), vec![2, 1, 0]);
+    }
+}
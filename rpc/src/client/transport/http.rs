@@ -1,14 +1,17 @@
 //! HTTP-based transport for Tendermint RPC Client.
 
+use alloc::sync::Arc;
 use core::str::FromStr;
 use core::time::Duration;
 
 use async_trait::async_trait;
 use reqwest::{header, Proxy};
+use tokio::sync::OnceCell;
 
 use tendermint::{block::Height, evidence::Evidence, Hash};
 use tendermint_config::net;
 
+use crate::batch::{Batch, BatchResponse};
 use crate::client::{Client, CompatMode};
 use crate::dialect::{v0_34, v0_37, v0_38, Dialect, LatestDialect};
 use crate::endpoint;
@@ -53,12 +56,16 @@ pub struct HttpClient {
     inner: reqwest::Client,
     url: reqwest::Url,
     compat: CompatMode,
+    // Set if the compatibility mode is to be detected on first use, and
+    // shared between clones of the client.
+    detected_compat: Option<Arc<OnceCell<CompatMode>>>,
 }
 
 /// The builder pattern constructor for [`HttpClient`].
 pub struct Builder {
     url: HttpClientUrl,
-    compat: CompatMode,
+    compat: Option<CompatMode>,
+    detect_compat: bool,
     proxy_url: Option<HttpClientUrl>,
     user_agent: Option<String>,
     timeout: Duration,
@@ -70,7 +77,18 @@ impl Builder {
     ///
     /// The default is the latest protocol version supported by this crate.
     pub fn compat_mode(mut self, mode: CompatMode) -> Self {
-        self.compat = mode;
+        self.compat = Some(mode);
+        self
+    }
+
+    /// Detect the compatibility mode from the version reported by the node's
+    /// `/status` endpoint, before the first request that depends on it.
+    ///
+    /// If detection fails, the request fails with the error, and detection
+    /// is attempted again on the next request. A compatibility mode set
+    /// explicitly with [`Builder::compat_mode`] takes precedence.
+    pub fn detect_compat_mode(mut self) -> Self {
+        self.detect_compat = true;
         self
     }
 
@@ -134,10 +152,15 @@ impl Builder {
             }
         };
 
+        let detected_compat = match (self.compat, self.detect_compat) {
+            (None, true) => Some(Arc::new(OnceCell::new())),
+            _ => None,
+        };
         Ok(HttpClient {
             inner,
             url: self.url.into(),
-            compat: self.compat,
+            compat: self.compat.unwrap_or_default(),
+            detected_compat,
         })
     }
 }
@@ -147,7 +170,12 @@ impl HttpClient {
     /// URL. This avoids using the `Builder` and thus does not perform any
     /// validation of the configuration.
     pub fn new_from_parts(inner: reqwest::Client, url: reqwest::Url, compat: CompatMode) -> Self {
-        Self { inner, url, compat }
+        Self {
+            inner,
+            url,
+            compat,
+            detected_compat: None,
+        }
     }
 
     /// Construct a new Tendermint RPC HTTP/S client connecting to the given
@@ -182,7 +210,8 @@ impl HttpClient {
     pub fn builder(url: HttpClientUrl) -> Builder {
         Builder {
             url,
-            compat: Default::default(),
+            compat: None,
+            detect_compat: false,
             proxy_url: None,
             user_agent: None,
             timeout: Duration::from_secs(30),
@@ -197,14 +226,39 @@ impl HttpClient {
     /// as a result of version discovery over the `/status` endpoint.
     pub fn set_compat_mode(&mut self, compat: CompatMode) {
         self.compat = compat;
+        self.detected_compat = None;
+    }
+
+    /// The compatibility mode used by the client.
+    ///
+    /// If the client was built to detect the compatibility mode, and it has
+    /// not been detected yet, this queries the node's `/status` endpoint.
+    pub async fn compat_mode(&self) -> Result<CompatMode, Error> {
+        let Some(detected) = &self.detected_compat else {
+            return Ok(self.compat);
+        };
+        detected
+            .get_or_try_init(|| async {
+                // The `/status` endpoint is the same in all supported dialects
+                let status = self
+                    .perform_with_dialect(endpoint::status::Request, LatestDialect)
+                    .await?;
+                let compat = CompatMode::from_version(status.node_info.version)?;
+                tracing::debug!("Detected RPC compatibility mode {}", compat);
+                Ok(compat)
+            })
+            .await
+            .copied()
     }
 
     fn build_request<R>(&self, request: R) -> Result<reqwest::Request, Error>
     where
         R: RequestMessage,
     {
-        let request_body = request.into_json();
+        self.build_request_with_body(request.into_json())
+    }
 
+    fn build_request_with_body(&self, request_body: String) -> Result<reqwest::Request, Error> {
         tracing::debug!(url = %self.url, body = %request_body, "outgoing request");
 
         let mut builder = self
@@ -226,6 +280,42 @@ impl HttpClient {
         S: Dialect,
     {
         let request = self.build_request(request)?;
+        let response_body = self.execute(request).await?;
+        R::Response::from_string(&response_body).map(Into::into)
+    }
+
+    /// Send a request that was already serialized as JSON, and parse the
+    /// response to it as the response to `R`.
+    pub(crate) async fn perform_serialized<R, S>(
+        &self,
+        request_body: String,
+    ) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest<S>,
+        S: Dialect,
+    {
+        let request = self.build_request_with_body(request_body)?;
+        let response_body = self.execute(request).await?;
+        R::Response::from_string(&response_body).map(Into::into)
+    }
+
+    /// Send a batch of requests to the node in a single HTTP request.
+    ///
+    /// The responses are matched to their requests by ID, and each one can
+    /// be retrieved with [`BatchResponse::take`]. They are parsed according
+    /// to the [compatibility mode](Self::compat_mode) of this client. An
+    /// empty batch is not sent to the node.
+    pub async fn perform_batch(&self, batch: Batch) -> Result<BatchResponse, Error> {
+        if batch.is_empty() {
+            return Ok(BatchResponse::default());
+        }
+        let compat = self.compat_mode().await?;
+        let request = self.build_request_with_body(batch.into_json())?;
+        let response_body = self.execute(request).await?;
+        BatchResponse::from_string(&response_body, compat)
+    }
+
+    async fn execute(&self, request: reqwest::Request) -> Result<bytes::Bytes, Error> {
         let response = self.inner.execute(request).await.map_err(Error::http)?;
         let response_status = response.status();
         let response_body = response.bytes().await.map_err(Error::http)?;
@@ -244,7 +334,7 @@ impl HttpClient {
             return Err(Error::http_request_failed(response_status));
         }
 
-        R::Response::from_string(&response_body).map(Into::into)
+        Ok(response_body)
     }
 }
 
@@ -261,29 +351,49 @@ impl Client for HttpClient {
     where
         H: Into<Height> + Send,
     {
-        perform_with_compat!(self, endpoint::block::Request::new(height.into()))
+        perform_with_compat!(
+            self,
+            self.compat_mode().await?,
+            endpoint::block::Request::new(height.into())
+        )
     }
 
     async fn block_by_hash(
         &self,
         hash: tendermint::Hash,
     ) -> Result<endpoint::block_by_hash::Response, Error> {
-        perform_with_compat!(self, endpoint::block_by_hash::Request::new(hash))
+        perform_with_compat!(
+            self,
+            self.compat_mode().await?,
+            endpoint::block_by_hash::Request::new(hash)
+        )
     }
 
     async fn latest_block(&self) -> Result<endpoint::block::Response, Error> {
-        perform_with_compat!(self, endpoint::block::Request::default())
+        perform_with_compat!(
+            self,
+            self.compat_mode().await?,
+            endpoint::block::Request::default()
+        )
     }
 
     async fn block_results<H>(&self, height: H) -> Result<endpoint::block_results::Response, Error>
     where
         H: Into<Height> + Send,
     {
-        perform_with_compat!(self, endpoint::block_results::Request::new(height.into()))
+        perform_with_compat!(
+            self,
+            self.compat_mode().await?,
+            endpoint::block_results::Request::new(height.into())
+        )
     }
 
     async fn latest_block_results(&self) -> Result<endpoint::block_results::Response, Error> {
-        perform_with_compat!(self, endpoint::block_results::Request::default())
+        perform_with_compat!(
+            self,
+            self.compat_mode().await?,
+            endpoint::block_results::Request::default()
+        )
     }
 
     async fn block_search(
@@ -295,6 +405,7 @@ impl Client for HttpClient {
     ) -> Result<endpoint::block_search::Response, Error> {
         perform_with_compat!(
             self,
+            self.compat_mode().await?,
             endpoint::block_search::Request::new(query, page, per_page, order)
         )
     }
@@ -304,7 +415,7 @@ impl Client for HttpClient {
         H: Into<Height> + Send,
     {
         let height = height.into();
-        match self.compat {
+        match self.compat_mode().await? {
             CompatMode::V0_38 => {
                 self.perform_with_dialect(endpoint::header::Request::new(height), v0_38::Dialect)
                     .await
@@ -328,7 +439,7 @@ impl Client for HttpClient {
         &self,
         hash: Hash,
     ) -> Result<endpoint::header_by_hash::Response, Error> {
-        match self.compat {
+        match self.compat_mode().await? {
             CompatMode::V0_38 => {
                 self.perform_with_dialect(
                     endpoint::header_by_hash::Request::new(hash),
@@ -362,7 +473,7 @@ impl Client for HttpClient {
         &self,
         evidence: Evidence,
     ) -> Result<endpoint::evidence::Response, Error> {
-        match self.compat {
+        match self.compat_mode().await? {
             CompatMode::V0_38 => {
                 let request = endpoint::evidence::Request::new(evidence);
                 self.perform_with_dialect(request, crate::dialect::v0_38::Dialect)
@@ -382,7 +493,11 @@ impl Client for HttpClient {
     }
 
     async fn tx(&self, hash: Hash, prove: bool) -> Result<endpoint::tx::Response, Error> {
-        perform_with_compat!(self, endpoint::tx::Request::new(hash, prove))
+        perform_with_compat!(
+            self,
+            self.compat_mode().await?,
+            endpoint::tx::Request::new(hash, prove)
+        )
     }
 
     async fn tx_search(
@@ -395,6 +510,7 @@ impl Client for HttpClient {
     ) -> Result<endpoint::tx_search::Response, Error> {
         perform_with_compat!(
             self,
+            self.compat_mode().await?,
             endpoint::tx_search::Request::new(query, prove, page, per_page, order)
         )
     }
@@ -406,7 +522,22 @@ impl Client for HttpClient {
     where
         T: Into<Vec<u8>> + Send,
     {
-        perform_with_compat!(self, endpoint::broadcast::tx_commit::Request::new(tx))
+        perform_with_compat!(
+            self,
+            self.compat_mode().await?,
+            endpoint::broadcast::tx_commit::Request::new(tx)
+        )
+    }
+
+    async fn check_tx<T>(&self, tx: T) -> Result<endpoint::check_tx::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        perform_with_compat!(
+            self,
+            self.compat_mode().await?,
+            endpoint::check_tx::Request::new(tx)
+        )
     }
 }
 
@@ -478,7 +609,9 @@ mod tests {
     use reqwest::{header::AUTHORIZATION, Request};
 
     use super::HttpClient;
+    use crate::client::CompatMode;
     use crate::endpoint::abci_info;
+    use crate::error::ErrorDetail;
     use crate::Url;
 
     fn authorization(req: &Request) -> Option<&str> {
@@ -510,4 +643,32 @@ mod tests {
             .count();
         assert_eq!(num_auth_headers, 1);
     }
+
+    #[tokio::test]
+    async fn explicit_compat_mode_overrides_detection() {
+        // Nothing listens on this port, so any request would fail.
+        let url = Url::from_str("http://127.0.0.1:1").unwrap();
+        let client = HttpClient::builder(url.try_into().unwrap())
+            .detect_compat_mode()
+            .compat_mode(CompatMode::V0_34)
+            .build()
+            .unwrap();
+        assert_eq!(client.compat_mode().await.unwrap(), CompatMode::V0_34);
+    }
+
+    #[tokio::test]
+    async fn failed_detection_is_retried() {
+        let url = Url::from_str("http://127.0.0.1:1").unwrap();
+        let mut client = HttpClient::builder(url.try_into().unwrap())
+            .detect_compat_mode()
+            .build()
+            .unwrap();
+        for _ in 0..2 {
+            let err = client.compat_mode().await.unwrap_err();
+            assert!(matches!(err.detail(), ErrorDetail::Http(_)));
+        }
+
+        client.set_compat_mode(CompatMode::V0_37);
+        assert_eq!(client.compat_mode().await.unwrap(), CompatMode::V0_37);
+    }
 }
@@ -1,6 +1,7 @@
 //! Mock client implementation for use in testing.
 
 use alloc::collections::BTreeMap as HashMap;
+use core::str::FromStr;
 
 use async_trait::async_trait;
 
@@ -84,8 +85,11 @@ impl<M: MockRequestMatcher> MockClient<M> {
         )
     }
 
-    /// Publishes the given event to all subscribers whose query exactly
-    /// matches that of the event.
+    /// Publishes the given event to all subscribers whose query matches the
+    /// event, as evaluated by [`Query::matches_flattened`].
+    ///
+    /// Each subscriber receives the event with its own query. If the event
+    /// does not have a `tm.event` entry, one is derived from its data.
     pub fn publish(&self, ev: &Event) {
         self.driver_tx
             .send(DriverCommand::Publish(Box::new(ev.clone())))
@@ -190,7 +194,23 @@ impl MockClientDriver {
     }
 
     fn publish(&mut self, event: Event) {
-        self.router.publish_event(event);
+        let mut events = event.events.clone().unwrap_or_default();
+        if let Some(event_type) = event.event_type() {
+            events
+                .entry("tm.event".to_owned())
+                .or_insert_with(|| vec![event_type.to_string()]);
+        }
+
+        let queries: Vec<String> = self.router.queries().cloned().collect();
+        for query in queries {
+            if matches!(Query::from_str(&query), Ok(q) if q.matches_flattened(&events)) {
+                let event = Event {
+                    query: query.clone(),
+                    ..event.clone()
+                };
+                self.router.publish(query, Ok(event));
+            }
+        }
     }
 }
 
@@ -396,5 +416,33 @@ mod test {
             client.close();
             driver_hdl.await.unwrap().unwrap();
         }
+
+        #[tokio::test]
+        async fn mock_subscription_client_filters_events() {
+            let (client, driver) = MockClient::new(MockRequestMethodMatcher::default());
+            let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+            let event = read_event("subscribe_newblock_0").await;
+
+            let query = Query::from(EventType::NewBlock).and_exists("tm.event");
+            let matching = client.subscribe(query.clone()).await.unwrap();
+            let wrong_type = client.subscribe(EventType::Tx.into()).await.unwrap();
+            let wrong_condition = client
+                .subscribe(Query::from(EventType::NewBlock).and_exists("tx.hash"))
+                .await
+                .unwrap();
+
+            client.publish(&event);
+            client.close();
+            driver_hdl.await.unwrap().unwrap();
+
+            let received = matching.collect::<Vec<_>>().await;
+            assert_eq!(received.len(), 1);
+            let received = received[0].as_ref().unwrap();
+            assert_eq!(received.query, query.to_string());
+            assert_eq!(received.data, event.data);
+            assert!(wrong_type.collect::<Vec<_>>().await.is_empty());
+            assert!(wrong_condition.collect::<Vec<_>>().await.is_empty());
+        }
     }
 }
@@ -113,6 +113,12 @@ impl SubscriptionRouter {
             .map(|subs_for_query| subs_for_query.len())
             .unwrap_or(0)
     }
+
+    /// The queries for which there are subscriptions.
+    #[cfg_attr(not(feature = "mock-client"), allow(dead_code))]
+    pub fn queries(&self) -> impl Iterator<Item = &SubscriptionQuery> {
+        self.subscriptions.keys()
+    }
 }
 
 #[cfg(feature = "websocket-client")]
@@ -124,6 +130,29 @@ impl SubscriptionRouter {
             .map(|subs_for_query| subs_for_query.len())
             .unwrap_or(0)
     }
+
+    /// Returns each query that still has subscribers, along with the ID of
+    /// one of its subscriptions.
+    pub fn active_queries(&self) -> Vec<(SubscriptionQuery, SubscriptionId)> {
+        self.subscriptions
+            .iter()
+            .filter_map(|(query, subs_for_query)| {
+                let id = subs_for_query.keys().next()?;
+                Some((query.clone(), id.clone()))
+            })
+            .collect()
+    }
+
+    /// Publishes the given error to all subscriptions, regardless of their
+    /// query.
+    pub fn publish_error_to_all(&mut self, err: Error) {
+        let queries: Vec<SubscriptionQuery> = self.subscriptions.keys().cloned().collect();
+        for query in queries {
+            if let PublishResult::AllDisconnected(query) = self.publish(query, Err(err.clone())) {
+                self.subscriptions.remove(&query);
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -277,4 +306,30 @@ mod test {
             assert_eq!(ev, subs3_ev);
         }
     }
+
+    #[cfg(feature = "websocket-client")]
+    #[tokio::test]
+    async fn router_publish_error_to_all() {
+        use crate::error::ErrorDetail;
+
+        let mut router = SubscriptionRouter::default();
+
+        let (subs1_id, subs2_id) = (uuid_str(), uuid_str());
+        let (subs1_event_tx, mut subs1_event_rx) = unbounded();
+        let (subs2_event_tx, subs2_event_rx) = unbounded();
+
+        router.add(subs1_id.clone(), "query1", subs1_event_tx);
+        router.add(subs2_id, "query2", subs2_event_tx);
+        drop(subs2_event_rx);
+
+        router.publish_error_to_all(Error::subscription_gap());
+
+        let err = must_recv(&mut subs1_event_rx, 500).await.unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::SubscriptionGap(_)));
+        // Subscriptions whose receivers were dropped are no longer tracked.
+        assert_eq!(
+            router.active_queries(),
+            vec![("query1".to_string(), subs1_id)]
+        );
+    }
 }
@@ -5,8 +5,11 @@ use core::{ops::Add, str::FromStr};
 
 use async_trait::async_trait;
 use async_tungstenite::{
-    tokio::ConnectStream,
+    tokio::{
+        connect_async_with_config, connect_async_with_tls_connector_and_config, ConnectStream,
+    },
     tungstenite::{
+        self,
         protocol::{frame::coding::CloseCode, CloseFrame},
         Message,
     },
@@ -30,7 +33,7 @@ use crate::{
     },
     dialect::{v0_34, Dialect, LatestDialect},
     endpoint::{self, subscribe, unsubscribe},
-    error::Error,
+    error::{Error, ErrorDetail},
     event::{self, Event},
     prelude::*,
     query::Query,
@@ -81,6 +84,21 @@ pub use async_tungstenite::tungstenite::protocol::WebSocketConfig;
 ///
 /// This is not configurable at present.
 ///
+/// ### Reconnection
+///
+/// By default, the driver terminates as soon as the connection fails. If a
+/// [`ReconnectPolicy`] is given to the [`Builder`], the driver instead
+/// reconnects to the node, backing off exponentially between attempts. Once
+/// reconnected, it:
+///
+/// - sends an [`ErrorDetail::SubscriptionGap`] error to every active
+///   subscription, as events published while disconnected are lost,
+/// - re-subscribes to the queries of all active subscriptions, and
+/// - re-sends the requests that had not been answered yet.
+///
+/// Commands issued by the client while the driver is reconnecting are
+/// handled once the connection is re-established.
+///
 /// ### Keep-Alive
 ///
 /// The WebSocket client implements a keep-alive mechanism whereby it sends a
@@ -143,8 +161,10 @@ pub struct WebSocketClient {
 /// The builder pattern constructor for [`WebSocketClient`].
 pub struct Builder {
     url: WebSocketClientUrl,
-    compat: CompatMode,
+    compat: Option<CompatMode>,
+    detect_compat: bool,
     transport_config: Option<WebSocketConfig>,
+    reconnect_policy: Option<ReconnectPolicy>,
 }
 
 impl Builder {
@@ -152,7 +172,18 @@ impl Builder {
     ///
     /// The default is the latest protocol version supported by this crate.
     pub fn compat_mode(mut self, mode: CompatMode) -> Self {
-        self.compat = mode;
+        self.compat = Some(mode);
+        self
+    }
+
+    /// Detect the compatibility mode from the version reported by the node's
+    /// `/status` endpoint, when connecting.
+    ///
+    /// A compatibility mode set explicitly with [`Builder::compat_mode`]
+    /// takes precedence. The detected mode is kept when the driver
+    /// reconnects.
+    pub fn detect_compat_mode(mut self) -> Self {
+        self.detect_compat = true;
         self
     }
 
@@ -162,20 +193,94 @@ impl Builder {
         self
     }
 
+    /// Reconnect automatically when the connection fails, according to the
+    /// given policy.
+    ///
+    /// By default, the driver does not reconnect.
+    pub fn reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = Some(policy);
+        self
+    }
+
     /// Try to create a client with the options specified for this builder.
     pub async fn build(self) -> Result<(WebSocketClient, WebSocketClientDriver), Error> {
         let url = self.url.0;
-        let compat = self.compat;
+        // Leaving the mode unset makes the connection detect it
+        let compat = match (self.compat, self.detect_compat) {
+            (None, false) => Some(CompatMode::default()),
+            (compat, _) => compat,
+        };
         let (inner, driver) = if url.is_secure() {
             sealed::WebSocketClient::new_secure(url, compat, self.transport_config).await?
         } else {
             sealed::WebSocketClient::new_unsecure(url, compat, self.transport_config).await?
         };
+        let driver = driver.with_reconnect_policy(self.reconnect_policy);
+        let compat = driver.compat;
 
         Ok((WebSocketClient { inner, compat }, driver))
     }
 }
 
+/// Policy for re-establishing a dropped WebSocket connection.
+///
+/// The delay before each attempt doubles, starting from the initial backoff
+/// and up to the maximum backoff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectPolicy {
+    /// Retry indefinitely, with delays ranging from 500 milliseconds to 30
+    /// seconds.
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_attempts: None,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// The delay before the first reconnection attempt.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// The maximum delay between two reconnection attempts.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Give up after the given number of consecutive failed attempts, in
+    /// which case [`WebSocketClientDriver::run`] returns the error that
+    /// caused the connection to be dropped.
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = Some(attempts);
+        self
+    }
+
+    /// The delay before the given (zero-based) reconnection attempt.
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2_u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+
+    fn allows_attempt(&self, attempt: u32) -> bool {
+        match self.max_attempts {
+            Some(max) => attempt < max,
+            None => true,
+        }
+    }
+}
+
 impl WebSocketClient {
     /// Construct a new WebSocket-based client connecting to the given
     /// Tendermint node's RPC endpoint.
@@ -211,8 +316,10 @@ impl WebSocketClient {
     pub fn builder(url: WebSocketClientUrl) -> Builder {
         Builder {
             url,
-            compat: Default::default(),
+            compat: None,
+            detect_compat: false,
             transport_config: Default::default(),
+            reconnect_policy: None,
         }
     }
 
@@ -349,6 +456,13 @@ impl Client for WebSocketClient {
     {
         perform_with_compat!(self, endpoint::broadcast::tx_commit::Request::new(tx))
     }
+
+    async fn check_tx<T>(&self, tx: T) -> Result<endpoint::check_tx::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        perform_with_compat!(self, endpoint::check_tx::Request::new(tx))
+    }
 }
 
 #[async_trait]
@@ -432,16 +546,80 @@ impl From<WebSocketClientUrl> for Url {
     }
 }
 
+/// Open a WebSocket connection to the given URL, over TLS if the URL is
+/// secure.
+async fn connect(
+    url: &Url,
+    config: Option<WebSocketConfig>,
+) -> Result<WebSocketStream<ConnectStream>, Error> {
+    let (stream, _response) = if url.is_secure() {
+        // Not supplying a connector means async_tungstenite will create the
+        // connector for us.
+        connect_async_with_tls_connector_and_config(url.clone(), None, config).await
+    } else {
+        connect_async_with_config(url.clone(), config).await
+    }
+    .map_err(Error::tungstenite)?;
+
+    Ok(stream)
+}
+
+/// Query the version of the node over a newly opened connection, before it is
+/// handed over to the driver, to determine the compatibility mode.
+async fn detect_compat_mode(
+    stream: &mut WebSocketStream<ConnectStream>,
+) -> Result<CompatMode, Error> {
+    let wrapper = Wrapper::new(endpoint::status::Request);
+    let id = wrapper.id().clone();
+    stream
+        .send(Message::Text(wrapper.into_json()))
+        .await
+        .map_err(|e| Error::web_socket("failed to write to WebSocket connection".to_string(), e))?;
+
+    let deadline = Instant::now() + RECV_TIMEOUT;
+    loop {
+        let msg = tokio::time::timeout_at(deadline, stream.next())
+            .await
+            .map_err(|_| Error::web_socket_timeout(RECV_TIMEOUT))?;
+        match msg {
+            Some(Ok(Message::Text(s))) => {
+                let wrapper: response::Wrapper<endpoint::status::Response> =
+                    serde_json::from_str(&s).map_err(Error::serde)?;
+                if wrapper.id() != &id {
+                    continue;
+                }
+                let status = wrapper.into_result()?;
+                let compat = CompatMode::from_version(status.node_info.version)?;
+                debug!("Detected RPC compatibility mode {}", compat);
+                return Ok(compat);
+            },
+            Some(Ok(Message::Ping(v))) => {
+                let _ = stream.send(Message::Pong(v)).await;
+            },
+            Some(Ok(Message::Close(_))) | None => {
+                return Err(Error::client_internal(
+                    "WebSocket connection closed while detecting the compatibility mode"
+                        .to_string(),
+                ))
+            },
+            Some(Ok(_)) => {},
+            Some(Err(e)) => {
+                return Err(Error::web_socket(
+                    "failed to read from WebSocket connection".to_string(),
+                    e,
+                ))
+            },
+        }
+    }
+}
+
 mod sealed {
-    use async_tungstenite::{
-        tokio::{connect_async_with_config, connect_async_with_tls_connector_and_config},
-        tungstenite::client::IntoClientRequest,
-    };
+    use async_tungstenite::tungstenite::client::IntoClientRequest;
     use tracing::debug;
 
     use super::{
-        DriverCommand, SimpleRequestCommand, SubscribeCommand, UnsubscribeCommand,
-        WebSocketClientDriver, WebSocketConfig,
+        connect, detect_compat_mode, DriverCommand, SimpleRequestCommand, SubscribeCommand,
+        UnsubscribeCommand, WebSocketClientDriver, WebSocketConfig,
     };
     use crate::{
         client::{
@@ -490,17 +668,19 @@ mod sealed {
         /// doesn't block the client.
         pub async fn new(
             url: Url,
-            compat: CompatMode,
+            compat: Option<CompatMode>,
             config: Option<WebSocketConfig>,
         ) -> Result<(Self, WebSocketClientDriver), Error> {
             debug!("Connecting to unsecure WebSocket endpoint: {}", url);
 
-            let (stream, _response) = connect_async_with_config(url, config)
-                .await
-                .map_err(Error::tungstenite)?;
+            let mut stream = connect(&url, config).await?;
+            let compat = match compat {
+                Some(compat) => compat,
+                None => detect_compat_mode(&mut stream).await?,
+            };
 
             let (cmd_tx, cmd_rx) = unbounded();
-            let driver = WebSocketClientDriver::new(stream, cmd_rx, compat);
+            let driver = WebSocketClientDriver::new(stream, cmd_rx, compat, url, config);
             let client = Self {
                 cmd_tx,
                 _client_type: Default::default(),
@@ -522,20 +702,19 @@ mod sealed {
         /// doesn't block the client.
         pub async fn new(
             url: Url,
-            compat: CompatMode,
+            compat: Option<CompatMode>,
             config: Option<WebSocketConfig>,
         ) -> Result<(Self, WebSocketClientDriver), Error> {
             debug!("Connecting to secure WebSocket endpoint: {}", url);
 
-            // Not supplying a connector means async_tungstenite will create the
-            // connector for us.
-            let (stream, _response) =
-                connect_async_with_tls_connector_and_config(url, None, config)
-                    .await
-                    .map_err(Error::tungstenite)?;
+            let mut stream = connect(&url, config).await?;
+            let compat = match compat {
+                Some(compat) => compat,
+                None => detect_compat_mode(&mut stream).await?,
+            };
 
             let (cmd_tx, cmd_rx) = unbounded();
-            let driver = WebSocketClientDriver::new(stream, cmd_rx, compat);
+            let driver = WebSocketClientDriver::new(stream, cmd_rx, compat, url, config);
             let client = Self {
                 cmd_tx,
                 _client_type: Default::default(),
@@ -627,7 +806,7 @@ mod sealed {
     impl WebSocketClient {
         pub async fn new_unsecure(
             url: Url,
-            compat: CompatMode,
+            compat: Option<CompatMode>,
             config: Option<WebSocketConfig>,
         ) -> Result<(Self, WebSocketClientDriver), Error> {
             let (client, driver) =
@@ -637,7 +816,7 @@ mod sealed {
 
         pub async fn new_secure(
             url: Url,
-            compat: CompatMode,
+            compat: Option<CompatMode>,
             config: Option<WebSocketConfig>,
         ) -> Result<(Self, WebSocketClientDriver), Error> {
             let (client, driver) =
@@ -756,6 +935,14 @@ struct SimpleRequestCommand {
     response_tx: ChannelTx<Result<String, Error>>,
 }
 
+// Whether the given error means that the connection to the node was lost.
+fn is_connection_error(e: &Error) -> bool {
+    matches!(
+        e.detail(),
+        ErrorDetail::WebSocket(_) | ErrorDetail::WebSocketTimeout(_) | ErrorDetail::Tungstenite(_)
+    )
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct GenericJsonResponse(serde_json::Value);
 
@@ -777,6 +964,10 @@ pub struct WebSocketClientDriver {
     pending_commands: HashMap<SubscriptionId, DriverCommand>,
     // The compatibility mode directing how to parse subscription events.
     compat: CompatMode,
+    // Where and how to reconnect if the connection fails.
+    url: Url,
+    transport_config: Option<WebSocketConfig>,
+    reconnect_policy: Option<ReconnectPolicy>,
 }
 
 impl WebSocketClientDriver {
@@ -784,6 +975,8 @@ impl WebSocketClientDriver {
         stream: WebSocketStream<ConnectStream>,
         cmd_rx: ChannelRx<DriverCommand>,
         compat: CompatMode,
+        url: Url,
+        transport_config: Option<WebSocketConfig>,
     ) -> Self {
         Self {
             stream,
@@ -791,9 +984,17 @@ impl WebSocketClientDriver {
             cmd_rx,
             pending_commands: HashMap::new(),
             compat,
+            url,
+            transport_config,
+            reconnect_policy: None,
         }
     }
 
+    fn with_reconnect_policy(mut self, policy: Option<ReconnectPolicy>) -> Self {
+        self.reconnect_policy = policy;
+        self
+    }
+
     async fn send_msg(&mut self, msg: Message) -> Result<(), Error> {
         self.stream.send(msg).await.map_err(|e| {
             Error::web_socket("failed to write to WebSocket connection".to_string(), e)
@@ -805,7 +1006,14 @@ impl WebSocketClientDriver {
             .send_msg(Message::Text(cmd.wrapped_request.clone()))
             .await
         {
-            cmd.response_tx.send(Err(e.clone()))?;
+            // If we are going to reconnect, the request is re-sent once the
+            // connection is re-established.
+            if self.reconnect_policy.is_some() {
+                self.pending_commands
+                    .insert(cmd.id.clone(), DriverCommand::SimpleRequest(cmd));
+            } else {
+                cmd.response_tx.send(Err(e.clone()))?;
+            }
             return Err(e);
         }
         self.pending_commands
@@ -823,35 +1031,137 @@ impl WebSocketClientDriver {
         tokio::pin!(recv_timeout);
 
         loop {
-            tokio::select! {
-                Some(res) = self.stream.next() => match res {
-                    Ok(msg) => {
+            let res = tokio::select! {
+                res = self.stream.next() => match res {
+                    Some(Ok(msg)) => {
                         // Reset the receive timeout every time we successfully
                         // receive a message from the remote endpoint.
                         recv_timeout.as_mut().reset(Instant::now().add(RECV_TIMEOUT));
-                        self.handle_incoming_msg(msg).await?
+                        self.handle_incoming_msg(msg).await
                     },
-                    Err(e) => return Err(
+                    Some(Err(e)) => Err(
                         Error::web_socket(
                             "failed to read from WebSocket connection".to_string(),
                             e
                         ),
                     ),
+                    // The remote endpoint closed the connection.
+                    None => Err(
+                        Error::web_socket(
+                            "WebSocket connection closed by the remote endpoint".to_string(),
+                            tungstenite::Error::ConnectionClosed,
+                        ),
+                    ),
                 },
                 Some(cmd) = self.cmd_rx.recv() => match cmd {
-                    DriverCommand::Subscribe(subs_cmd) => self.subscribe(subs_cmd).await?,
-                    DriverCommand::Unsubscribe(unsubs_cmd) => self.unsubscribe(unsubs_cmd).await?,
-                    DriverCommand::SimpleRequest(req_cmd) => self.simple_request(req_cmd).await?,
+                    DriverCommand::Subscribe(subs_cmd) => self.subscribe(subs_cmd).await,
+                    DriverCommand::Unsubscribe(unsubs_cmd) => self.unsubscribe(unsubs_cmd).await,
+                    DriverCommand::SimpleRequest(req_cmd) => self.simple_request(req_cmd).await,
                     DriverCommand::Terminate => return self.close().await,
                 },
-                _ = ping_interval.tick() => self.ping().await?,
-                _ = &mut recv_timeout => {
-                    return Err(Error::web_socket_timeout(RECV_TIMEOUT));
+                _ = ping_interval.tick() => self.ping().await,
+                _ = &mut recv_timeout => Err(Error::web_socket_timeout(RECV_TIMEOUT)),
+            };
+
+            if let Err(e) = res {
+                match self.reconnect_policy {
+                    Some(policy) if is_connection_error(&e) => {
+                        error!("WebSocket connection failed, reconnecting: {}", e);
+                        self.reconnect(policy, e).await?;
+                        ping_interval.reset();
+                        recv_timeout
+                            .as_mut()
+                            .reset(Instant::now().add(RECV_TIMEOUT));
+                    },
+                    _ => return Err(e),
                 }
             }
         }
     }
 
+    /// Re-establish the connection, then resume the active subscriptions and
+    /// pending requests.
+    ///
+    /// Returns the error that caused the connection to be dropped if the
+    /// policy's maximum number of attempts is exhausted.
+    async fn reconnect(&mut self, policy: ReconnectPolicy, cause: Error) -> Result<(), Error> {
+        // Events published while we are disconnected are lost.
+        self.router.publish_error_to_all(Error::subscription_gap());
+
+        let mut attempt = 0;
+        while policy.allows_attempt(attempt) {
+            tokio::time::sleep(policy.backoff(attempt)).await;
+            attempt += 1;
+
+            debug!(
+                "Reconnecting to WebSocket endpoint {} (attempt {})",
+                self.url, attempt
+            );
+            match connect(&self.url, self.transport_config).await {
+                Ok(stream) => self.stream = stream,
+                Err(e) => {
+                    error!("Failed to reconnect to WebSocket endpoint: {}", e);
+                    continue;
+                },
+            }
+            match self.resume().await {
+                Ok(()) => return Ok(()),
+                Err(e) => error!("Failed to resume subscriptions after reconnecting: {}", e),
+            }
+        }
+        Err(cause)
+    }
+
+    async fn resume(&mut self) -> Result<(), Error> {
+        // The node dropped our subscriptions along with the connection. We
+        // re-subscribe using the ID of one of the existing subscriptions, so
+        // that any error in response is routed to the subscribers.
+        for (query, id) in self.router.active_queries() {
+            self.send_request(Wrapper::new_with_id(
+                Id::Str(id),
+                subscribe::Request::new(query),
+            ))
+            .await?;
+        }
+
+        // The subscriptions of pending unsubscribe commands were already
+        // removed from the router, and the node forgot about them when the
+        // connection dropped.
+        let unsubscribed: Vec<_> = self
+            .pending_commands
+            .iter()
+            .filter(|(_, cmd)| matches!(cmd, DriverCommand::Unsubscribe(_)))
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in unsubscribed {
+            if let Some(DriverCommand::Unsubscribe(cmd)) = self.pending_commands.remove(&id) {
+                let _ = cmd.response_tx.send(Ok(()));
+            }
+        }
+
+        // The other pending commands stay pending until they are answered,
+        // even if re-sending them fails and we have to reconnect again.
+        let msgs: Vec<_> = self
+            .pending_commands
+            .values()
+            .filter_map(|cmd| match cmd {
+                DriverCommand::SimpleRequest(req_cmd) => Some(req_cmd.wrapped_request.clone()),
+                DriverCommand::Subscribe(subs_cmd) => Some(
+                    serde_json::to_string_pretty(&Wrapper::new_with_id(
+                        Id::Str(subs_cmd.id.clone()),
+                        subscribe::Request::new(subs_cmd.query.clone()),
+                    ))
+                    .unwrap(),
+                ),
+                _ => None,
+            })
+            .collect();
+        for msg in msgs {
+            self.send_msg(Message::Text(msg)).await?;
+        }
+        Ok(())
+    }
+
     async fn send_request<R>(&mut self, wrapper: Wrapper<R>) -> Result<(), Error>
     where
         R: Request,
@@ -879,7 +1189,12 @@ impl WebSocketClientDriver {
             subscribe::Request::new(cmd.query.clone()),
         );
         if let Err(e) = self.send_request(wrapper).await {
-            cmd.response_tx.send(Err(e.clone()))?;
+            if self.reconnect_policy.is_some() {
+                self.pending_commands
+                    .insert(cmd.id.clone(), DriverCommand::Subscribe(cmd));
+            } else {
+                cmd.response_tx.send(Err(e.clone()))?;
+            }
             return Err(e);
         }
         self.pending_commands
@@ -903,7 +1218,13 @@ impl WebSocketClientDriver {
         let wrapper = Wrapper::new(unsubscribe::Request::new(cmd.query.clone()));
         let req_id = wrapper.id().clone();
         if let Err(e) = self.send_request(wrapper).await {
-            cmd.response_tx.send(Err(e.clone()))?;
+            // The subscriptions are already gone from the router, so they
+            // will not be resumed if we reconnect.
+            if self.reconnect_policy.is_some() {
+                cmd.response_tx.send(Ok(()))?;
+            } else {
+                cmd.response_tx.send(Err(e.clone()))?;
+            }
             return Err(e);
         }
         self.pending_commands
@@ -1080,8 +1401,35 @@ mod test {
         V0_38,
     }
 
+    // How the test server ends the first connection it accepts, upon
+    // receiving a `/health` request, which lets the tests exercise
+    // reconnection.
+    #[derive(Copy, Clone)]
+    enum Disconnect {
+        // Drop the connection without closing it.
+        Drop,
+        // Close the connection with a close frame.
+        Close,
+    }
+
     impl TestServer {
         async fn new(addr: &str, version: TestRpcVersion) -> Self {
+            Self::with_disconnect(addr, version, None).await
+        }
+
+        async fn disconnecting(
+            addr: &str,
+            version: TestRpcVersion,
+            disconnect: Disconnect,
+        ) -> Self {
+            Self::with_disconnect(addr, version, Some(disconnect)).await
+        }
+
+        async fn with_disconnect(
+            addr: &str,
+            version: TestRpcVersion,
+            disconnect: Option<Disconnect>,
+        ) -> Self {
             let listener = TcpListener::bind(addr).await.unwrap();
             let local_addr = listener.local_addr().unwrap();
             let node_addr = net::Address::Tcp {
@@ -1091,7 +1439,8 @@ mod test {
             };
             let (terminate_tx, terminate_rx) = unbounded();
             let (event_tx, event_rx) = unbounded();
-            let driver = TestServerDriver::new(listener, version, event_rx, terminate_rx);
+            let driver =
+                TestServerDriver::new(listener, version, disconnect, event_rx, terminate_rx);
             let driver_hdl = tokio::spawn(async move { driver.run().await });
             Self {
                 node_addr,
@@ -1115,6 +1464,7 @@ mod test {
     struct TestServerDriver {
         listener: TcpListener,
         version: TestRpcVersion,
+        disconnect: Option<Disconnect>,
         event_rx: ChannelRx<Event>,
         terminate_rx: ChannelRx<Result<(), Error>>,
         handlers: Vec<TestServerHandler>,
@@ -1124,12 +1474,14 @@ mod test {
         fn new(
             listener: TcpListener,
             version: TestRpcVersion,
+            disconnect: Option<Disconnect>,
             event_rx: ChannelRx<Event>,
             terminate_rx: ChannelRx<Result<(), Error>>,
         ) -> Self {
             Self {
                 listener,
                 version,
+                disconnect,
                 event_rx,
                 terminate_rx,
                 handlers: Vec::new(),
@@ -1161,8 +1513,10 @@ mod test {
         }
 
         async fn handle_incoming(&mut self, stream: TcpStream) {
+            // Only the first connection is ended.
+            let disconnect = self.disconnect.take();
             self.handlers
-                .push(TestServerHandler::new(stream, self.version).await);
+                .push(TestServerHandler::new(stream, self.version, disconnect).await);
         }
 
         async fn terminate(&mut self) {
@@ -1185,12 +1539,17 @@ mod test {
     }
 
     impl TestServerHandler {
-        async fn new(stream: TcpStream, version: TestRpcVersion) -> Self {
+        async fn new(
+            stream: TcpStream,
+            version: TestRpcVersion,
+            disconnect: Option<Disconnect>,
+        ) -> Self {
             let conn: WebSocketStream<TokioAdapter<TcpStream>> =
                 accept_async(stream).await.unwrap();
             let (terminate_tx, terminate_rx) = unbounded();
             let (event_tx, event_rx) = unbounded();
-            let driver = TestServerHandlerDriver::new(conn, version, event_rx, terminate_rx);
+            let driver =
+                TestServerHandlerDriver::new(conn, version, disconnect, event_rx, terminate_rx);
             let driver_hdl = tokio::spawn(async move { driver.run().await });
             Self {
                 driver_hdl,
@@ -1213,6 +1572,9 @@ mod test {
     struct TestServerHandlerDriver {
         conn: WebSocketStream<TokioAdapter<TcpStream>>,
         version: TestRpcVersion,
+        // How to end the connection instead of responding to a `/health`
+        // request, if at all.
+        disconnect: Option<Disconnect>,
         event_rx: ChannelRx<Event>,
         terminate_rx: ChannelRx<Result<(), Error>>,
         // A mapping of subscription queries to subscription IDs for this
@@ -1224,12 +1586,14 @@ mod test {
         fn new(
             conn: WebSocketStream<TokioAdapter<TcpStream>>,
             version: TestRpcVersion,
+            disconnect: Option<Disconnect>,
             event_rx: ChannelRx<Event>,
             terminate_rx: ChannelRx<Result<(), Error>>,
         ) -> Self {
             Self {
                 conn,
                 version,
+                disconnect,
                 event_rx,
                 terminate_rx,
                 subscriptions: HashMap::new(),
@@ -1316,6 +1680,38 @@ mod test {
                                     self.remove_subscription(req.params().query.clone());
                                     self.send(req.id().clone(), unsubscribe::Response {}).await;
                                 },
+                                Method::Status => {
+                                    let req = serde_json::from_str::<
+                                        request::Wrapper<endpoint::status::Request>,
+                                    >(&msg)
+                                    .unwrap();
+                                    let version = match self.version {
+                                        TestRpcVersion::V0_34 => "v0_34",
+                                        TestRpcVersion::V0_37 => "v0_37",
+                                        TestRpcVersion::V0_38 => "v0_38",
+                                    };
+                                    let res = endpoint::status::Response::from_string(
+                                        read_json_fixture(version, "status").await,
+                                    )
+                                    .unwrap();
+                                    self.send(req.id().clone(), res).await;
+                                },
+                                Method::Health => {
+                                    match self.disconnect {
+                                        Some(Disconnect::Drop) => return Some(Ok(())),
+                                        Some(Disconnect::Close) => {
+                                            self.terminate().await;
+                                            return Some(Ok(()));
+                                        },
+                                        None => {},
+                                    }
+                                    let req = serde_json::from_str::<
+                                        request::Wrapper<endpoint::health::Request>,
+                                    >(&msg)
+                                    .unwrap();
+                                    self.send(req.id().clone(), endpoint::health::Response {})
+                                        .await;
+                                },
                                 _ => {
                                     println!("Unsupported method in incoming request: {}", &method);
                                 },
@@ -1513,6 +1909,35 @@ mod test {
                 );
             }
         }
+
+        #[tokio::test]
+        async fn websocket_client_detects_compat_mode() {
+            let server = TestServer::new("127.0.0.1:0", TestRpcVersion::V0_37).await;
+            let url: WebSocketClientUrl = server.node_addr.clone().try_into().unwrap();
+
+            let (client, driver) = WebSocketClient::builder(url.clone())
+                .detect_compat_mode()
+                .build()
+                .await
+                .unwrap();
+            assert_eq!(client.compat, CompatMode::V0_37);
+            assert_eq!(driver.compat, CompatMode::V0_37);
+            client.close().unwrap();
+            driver.run().await.unwrap();
+
+            // An explicitly set mode takes precedence.
+            let (client, driver) = WebSocketClient::builder(url)
+                .detect_compat_mode()
+                .compat_mode(CompatMode::V0_34)
+                .build()
+                .await
+                .unwrap();
+            assert_eq!(client.compat, CompatMode::V0_34);
+            client.close().unwrap();
+            driver.run().await.unwrap();
+
+            server.terminate().await.unwrap();
+        }
     }
 
     mod v0_38 {
@@ -1580,6 +2005,56 @@ mod test {
                 );
             }
         }
+
+        async fn resumes_after_reconnecting(disconnect: Disconnect) {
+            let event = read_event("subscribe_newblock_0").await;
+
+            let mut server =
+                TestServer::disconnecting("127.0.0.1:0", TestRpcVersion::V0_38, disconnect).await;
+            let url = server.node_addr.clone().try_into().unwrap();
+            let (client, driver) = WebSocketClient::builder(url)
+                .compat_mode(CompatMode::V0_38)
+                .reconnect_policy(
+                    ReconnectPolicy::default()
+                        .initial_backoff(Duration::from_millis(10))
+                        .max_attempts(5),
+                )
+                .build()
+                .await
+                .unwrap();
+            let driver_handle = tokio::spawn(async move { driver.run().await });
+
+            let mut subs = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+
+            // The server ends the first connection upon receiving this
+            // request, so it is only answered once it has been re-sent over
+            // the new connection.
+            client.health().await.unwrap();
+
+            // The subscribers learn that they may have missed events, once.
+            let err = subs.next().await.unwrap().unwrap_err();
+            assert!(matches!(err.detail(), ErrorDetail::SubscriptionGap(_)));
+
+            // The new connection only knows about the subscription if the
+            // client re-subscribed, which it did before re-sending the
+            // request answered above.
+            server.publish_event(event.clone()).unwrap();
+            assert_eq!(subs.next().await.unwrap().unwrap(), event);
+
+            client.close().unwrap();
+            server.terminate().await.unwrap();
+            driver_handle.await.unwrap().unwrap();
+        }
+
+        #[tokio::test]
+        async fn websocket_client_resumes_after_reconnecting() {
+            resumes_after_reconnecting(Disconnect::Drop).await;
+        }
+
+        #[tokio::test]
+        async fn websocket_client_reconnects_after_connection_close() {
+            resumes_after_reconnecting(Disconnect::Close).await;
+        }
     }
 
     fn authorization(req: &http::Request<()>) -> Option<&str> {
@@ -1603,4 +2078,19 @@ mod test {
 
         assert_eq!(authorization(&req), None);
     }
+
+    #[test]
+    fn reconnect_backoff() {
+        let policy = ReconnectPolicy::default()
+            .initial_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(10))
+            .max_attempts(5);
+
+        let backoffs: Vec<u64> = (0..5).map(|i| policy.backoff(i).as_secs()).collect();
+        assert_eq!(backoffs, vec![1, 2, 4, 8, 10]);
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(10));
+        assert!(policy.allows_attempt(4));
+        assert!(!policy.allows_attempt(5));
+        assert!(ReconnectPolicy::default().allows_attempt(u32::MAX));
+    }
 }
@@ -8,9 +8,15 @@ pub mod block_results;
 pub mod block_search;
 pub mod blockchain;
 pub mod broadcast;
+pub mod check_tx;
 pub mod commit;
 pub mod consensus_params;
 pub mod consensus_state;
+#[cfg(feature = "unsafe")]
+pub mod dial_peers;
+#[cfg(feature = "unsafe")]
+pub mod dial_seeds;
+pub mod dump_consensus_state;
 pub mod evidence;
 pub mod genesis;
 pub mod genesis_chunked;
@@ -18,9 +24,13 @@ pub mod header;
 pub mod header_by_hash;
 pub mod health;
 pub mod net_info;
+pub mod num_unconfirmed_txs;
 pub mod status;
 pub mod subscribe;
 pub mod tx;
 pub mod tx_search;
+pub mod unconfirmed_txs;
+#[cfg(feature = "unsafe")]
+pub mod unsafe_flush_mempool;
 pub mod unsubscribe;
 pub mod validators;
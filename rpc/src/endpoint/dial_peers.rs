@@ -0,0 +1,101 @@
+//! `/dial_peers` endpoint JSON-RPC wrapper
+//!
+//! This endpoint is only available on nodes with the unsafe RPC routes
+//! enabled.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{dialect::Dialect, prelude::*, request::RequestMessage};
+
+/// Dial the given peers
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// Peer addresses, in the form `<node ID>@<host>:<port>`
+    pub peers: Vec<String>,
+
+    /// Add the peers to the node's persistent peers
+    pub persistent: bool,
+
+    /// Add the peers to the node's unconditional peers, which are not
+    /// subject to the inbound/outbound peer limits
+    pub unconditional: bool,
+
+    /// Add the peers to the node's private peers, whose addresses are not
+    /// gossiped to the rest of the network
+    pub private: bool,
+}
+
+impl Request {
+    /// Create a new request to dial the given peers, without any of the
+    /// persistent, unconditional or private flags set.
+    pub fn new(peers: Vec<String>) -> Self {
+        Self {
+            peers,
+            ..Default::default()
+        }
+    }
+
+    /// Mark the peers as persistent.
+    pub fn persistent(mut self, persistent: bool) -> Self {
+        self.persistent = persistent;
+        self
+    }
+
+    /// Mark the peers as unconditional.
+    pub fn unconditional(mut self, unconditional: bool) -> Self {
+        self.unconditional = unconditional;
+        self
+    }
+
+    /// Mark the peers as private.
+    pub fn private(mut self, private: bool) -> Self {
+        self.private = private;
+        self
+    }
+}
+
+impl RequestMessage for Request {
+    fn method(&self) -> crate::Method {
+        crate::Method::DialPeers
+    }
+}
+
+impl<S: Dialect> crate::Request<S> for Request {
+    type Response = Response;
+}
+
+impl<S: Dialect> crate::SimpleRequest<S> for Request {
+    type Output = Response;
+}
+
+/// Dial peers response
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// Log message from the node
+    pub log: String,
+}
+
+impl crate::Response for Response {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_request_flags() {
+        let request = Request::new(vec![
+            "2dd9f44fd9067555c322243c3c913ba7b51d2be0@127.0.0.1:26656".to_string(),
+        ])
+        .persistent(true)
+        .private(true);
+        assert_eq!(
+            serde_json::to_value(request).unwrap(),
+            serde_json::json!({
+                "peers": ["2dd9f44fd9067555c322243c3c913ba7b51d2be0@127.0.0.1:26656"],
+                "persistent": true,
+                "unconditional": false,
+                "private": true,
+            })
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! `/dial_seeds` endpoint JSON-RPC wrapper
+//!
+//! This endpoint is only available on nodes with the unsafe RPC routes
+//! enabled.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{dialect::Dialect, prelude::*, request::RequestMessage};
+
+/// Dial the given seed nodes
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// Seed addresses, in the form `<node ID>@<host>:<port>`
+    pub seeds: Vec<String>,
+}
+
+impl Request {
+    /// Create a new request to dial the given seeds.
+    pub fn new(seeds: Vec<String>) -> Self {
+        Self { seeds }
+    }
+}
+
+impl RequestMessage for Request {
+    fn method(&self) -> crate::Method {
+        crate::Method::DialSeeds
+    }
+}
+
+impl<S: Dialect> crate::Request<S> for Request {
+    type Response = Response;
+}
+
+impl<S: Dialect> crate::SimpleRequest<S> for Request {
+    type Output = Response;
+}
+
+/// Dial seeds response
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// Log message from the node
+    pub log: String,
+}
+
+impl crate::Response for Response {}
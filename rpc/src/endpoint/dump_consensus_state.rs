@@ -0,0 +1,352 @@
+//! `/dump_consensus_state` endpoint JSON-RPC wrapper
+
+use alloc::collections::BTreeMap;
+use core::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tendermint::{
+    block::{self, parts, Height, Round},
+    node, serializers, validator, Time,
+};
+
+use crate::{
+    dialect::Dialect,
+    endpoint::consensus_state::{HeightRoundStep, RoundVote, RoundVotes},
+    prelude::*,
+    request::RequestMessage,
+    Error, Method,
+};
+
+/// Get the complete consensus state, including the state of each peer.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request;
+
+impl Request {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl RequestMessage for Request {
+    fn method(&self) -> Method {
+        Method::DumpConsensusState
+    }
+}
+
+impl<S: Dialect> crate::Request<S> for Request {
+    type Response = Response;
+}
+
+impl<S: Dialect> crate::SimpleRequest<S> for Request {
+    type Output = Response;
+}
+
+/// The complete consensus state of the node and its peers (UNSTABLE).
+///
+/// Currently based on <https://github.com/cometbft/cometbft/blob/v0.38.0/rpc/core/types/responses.go#L181>
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    pub round_state: RoundState,
+    pub peers: Vec<PeerStateInfo>,
+}
+
+impl crate::Response for Response {}
+
+/// The internal consensus state of the node.
+///
+/// Blocks and proposals held by the consensus reactor are not included.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RoundState {
+    pub height: Height,
+    pub round: i32,
+    pub step: i8,
+
+    #[serde(with = "serializers::time")]
+    pub start_time: Time,
+
+    #[serde(with = "serializers::time")]
+    pub commit_time: Time,
+
+    pub validators: validator::Set,
+
+    pub locked_round: i32,
+    pub valid_round: i32,
+
+    pub votes: Vec<RoundVotes>,
+
+    pub commit_round: i32,
+
+    pub last_commit: Option<VoteSet>,
+
+    pub last_validators: validator::Set,
+
+    pub triggered_timeout_precommit: bool,
+}
+
+impl RoundState {
+    /// The height, round and step of the node, as reported by the
+    /// `/consensus_state` endpoint.
+    pub fn height_round_step(&self) -> Option<HeightRoundStep> {
+        height_round_step(self.height, self.round, self.step)
+    }
+}
+
+/// Summary of the votes collected for a particular round.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VoteSet {
+    pub votes: Vec<RoundVote>,
+    pub votes_bit_array: String,
+    pub peer_maj_23s: BTreeMap<node::Id, block::Id>,
+}
+
+/// Consensus state of a single peer, as tracked by the node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerStateInfo {
+    /// Address of the peer, in the form `<node ID>@<IP>:<port>`
+    pub node_address: String,
+    pub peer_state: PeerState,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerState {
+    pub round_state: PeerRoundState,
+    pub stats: PeerStats,
+}
+
+/// The known state of a peer's consensus round.
+///
+/// Based on <https://github.com/cometbft/cometbft/blob/v0.38.0/consensus/types/peer_round_state.go#L13>
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerRoundState {
+    pub height: Height,
+    pub round: i32,
+    pub step: i8,
+
+    #[serde(with = "serializers::time")]
+    pub start_time: Time,
+
+    /// True if the peer has the proposal for this round
+    pub proposal: bool,
+    pub proposal_block_part_set_header: parts::Header,
+    pub proposal_block_parts: Option<BitArray>,
+
+    /// Proof-of-lock round of the proposal, or -1 if there is none
+    pub proposal_pol_round: i32,
+    pub proposal_pol: Option<BitArray>,
+
+    pub prevotes: Option<BitArray>,
+    pub precommits: Option<BitArray>,
+
+    pub last_commit_round: i32,
+    pub last_commit: Option<BitArray>,
+
+    /// Round that we have commit for, or -1 if unknown
+    pub catchup_commit_round: i32,
+    pub catchup_commit: Option<BitArray>,
+}
+
+impl PeerRoundState {
+    /// The height, round and step of the peer, if the peer has started a
+    /// round.
+    pub fn height_round_step(&self) -> Option<HeightRoundStep> {
+        height_round_step(self.height, self.round, self.step)
+    }
+}
+
+/// Number of useful messages received from a peer.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerStats {
+    #[serde(with = "serializers::from_str")]
+    pub votes: u64,
+    #[serde(with = "serializers::from_str")]
+    pub block_parts: u64,
+}
+
+fn height_round_step(height: Height, round: i32, step: i8) -> Option<HeightRoundStep> {
+    Some(HeightRoundStep {
+        height,
+        round: Round::try_from(round).ok()?,
+        step,
+    })
+}
+
+/// A compact array of bits, one per validator, in the order of the
+/// validator set.
+///
+/// Serialized as a string of `x` (set) and `_` (unset) characters.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitArray(Vec<bool>);
+
+impl BitArray {
+    /// Number of bits in the array.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the bit at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        self.0.get(index).copied()
+    }
+
+    /// Number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.0.iter().filter(|bit| **bit).count()
+    }
+
+    /// Indices of the unset bits, e.g. the validators whose votes are
+    /// missing.
+    pub fn unset_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, bit)| (!bit).then_some(i))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl From<Vec<bool>> for BitArray {
+    fn from(bits: Vec<bool>) -> Self {
+        Self(bits)
+    }
+}
+
+impl FromStr for BitArray {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .map(|c| match c {
+                'x' => Ok(true),
+                '_' => Ok(false),
+                _ => Err(Error::client_internal(format!(
+                    "invalid character in bit array: {c:?}"
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for BitArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in &self.0 {
+            f.write_str(if *bit { "x" } else { "_" })?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for BitArray {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for BitArray {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bit_array_round_trip() {
+        let bits = BitArray::from_str("x_xx_").unwrap();
+        assert_eq!(bits.len(), 5);
+        assert_eq!(bits.count_ones(), 3);
+        assert_eq!(bits.get(1), Some(false));
+        assert_eq!(bits.get(5), None);
+        assert_eq!(bits.unset_indices().collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(bits.to_string(), "x_xx_");
+    }
+
+    #[test]
+    fn bit_array_rejects_invalid_characters() {
+        assert!(BitArray::from_str("x_y").is_err());
+    }
+
+    #[test]
+    fn peer_round_state_without_round() {
+        let state: PeerRoundState = serde_json::from_str(
+            r#"{
+                "height": "0",
+                "round": -1,
+                "step": 0,
+                "start_time": "0001-01-01T00:00:00Z",
+                "proposal": false,
+                "proposal_block_part_set_header": { "total": 0, "hash": "" },
+                "proposal_block_parts": null,
+                "proposal_pol_round": -1,
+                "proposal_pol": null,
+                "prevotes": null,
+                "precommits": null,
+                "last_commit_round": -1,
+                "last_commit": null,
+                "catchup_commit_round": -1,
+                "catchup_commit": null
+            }"#,
+        )
+        .unwrap();
+        assert!(state.height_round_step().is_none());
+        assert!(state.catchup_commit.is_none());
+    }
+
+    #[test]
+    fn peer_state_with_missing_votes() {
+        let info: PeerStateInfo = serde_json::from_str(
+            r#"{
+                "node_address": "2dd9f44fd9067555c322243c3c913ba7b51d2be0@127.0.0.1:26656",
+                "peer_state": {
+                    "round_state": {
+                        "height": "221",
+                        "round": 1,
+                        "step": 6,
+                        "start_time": "2023-05-17T14:14:43.338360213Z",
+                        "proposal": true,
+                        "proposal_block_part_set_header": {
+                            "total": 1,
+                            "hash": "A6C6F44F0D7BE2ED62B4CF5E0C4E9F5E8A0A25B2B1C8D1B0E5A4F7AE29E7D1C3"
+                        },
+                        "proposal_block_parts": "x",
+                        "proposal_pol_round": -1,
+                        "proposal_pol": "____",
+                        "prevotes": "xx_x",
+                        "precommits": "x___",
+                        "last_commit_round": 0,
+                        "last_commit": "xxxx",
+                        "catchup_commit_round": -1,
+                        "catchup_commit": "____"
+                    },
+                    "stats": { "votes": "42", "block_parts": "3" }
+                }
+            }"#,
+        )
+        .unwrap();
+        let state = info.peer_state.round_state;
+        let hrs = state.height_round_step().unwrap();
+        assert_eq!(hrs.height.value(), 221);
+        assert_eq!(hrs.round.value(), 1);
+        let prevotes = state.prevotes.unwrap();
+        assert_eq!(prevotes.unset_indices().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(state.precommits.unwrap().count_ones(), 1);
+        assert_eq!(state.catchup_commit.unwrap().count_ones(), 0);
+        assert_eq!(info.peer_state.stats.votes, 42);
+    }
+}
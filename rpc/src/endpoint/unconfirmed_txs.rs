@@ -0,0 +1,60 @@
+//! `/unconfirmed_txs` endpoint JSON-RPC wrapper
+
+use serde::{Deserialize, Serialize};
+
+use crate::{dialect::Dialect, prelude::*, request::RequestMessage, serializers};
+
+/// The default number of unconfirmed transactions returned by the node.
+pub const DEFAULT_UNCONFIRMED_TXS_LIMIT: u32 = 30;
+
+/// Get the unconfirmed transactions in the mempool of the node
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// The maximum number of transactions to return. If not specified, the
+    /// node returns [`DEFAULT_UNCONFIRMED_TXS_LIMIT`] transactions at most.
+    #[serde(with = "serializers::optional_from_str")]
+    pub limit: Option<u32>,
+}
+
+impl Request {
+    /// Create a new request for at most `limit` unconfirmed transactions.
+    pub fn new(limit: Option<u32>) -> Self {
+        Self { limit }
+    }
+}
+
+impl RequestMessage for Request {
+    fn method(&self) -> crate::Method {
+        crate::Method::UnconfirmedTxs
+    }
+}
+
+impl<S: Dialect> crate::Request<S> for Request {
+    type Response = Response;
+}
+
+impl<S: Dialect> crate::SimpleRequest<S> for Request {
+    type Output = Response;
+}
+
+/// Unconfirmed transactions response
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// Number of transactions returned
+    #[serde(with = "serializers::from_str")]
+    pub n_txs: u64,
+
+    /// Total number of transactions in the mempool
+    #[serde(with = "serializers::from_str")]
+    pub total: u64,
+
+    /// Total size of the transactions in the mempool, in bytes
+    #[serde(with = "serializers::from_str")]
+    pub total_bytes: u64,
+
+    /// Transactions
+    #[serde(with = "serializers::txs")]
+    pub txs: Vec<Vec<u8>>,
+}
+
+impl crate::Response for Response {}
@@ -0,0 +1,32 @@
+//! `/unsafe_flush_mempool` endpoint JSON-RPC wrapper
+//!
+//! This endpoint is only available on nodes with the unsafe RPC routes
+//! enabled.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{dialect::Dialect, request::RequestMessage};
+
+/// Remove all transactions from the mempool of the node
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request;
+
+impl RequestMessage for Request {
+    fn method(&self) -> crate::Method {
+        crate::Method::UnsafeFlushMempool
+    }
+}
+
+impl<S: Dialect> crate::Request<S> for Request {
+    type Response = Response;
+}
+
+impl<S: Dialect> crate::SimpleRequest<S> for Request {
+    type Output = Response;
+}
+
+/// Flush mempool response
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {}
+
+impl crate::Response for Response {}
@@ -129,6 +129,11 @@ define_error! {
         ChannelSend
             | _ | { "failed to send message to internal channel" },
 
+        SubscriptionGap
+            | _ | {
+                "WebSocket connection was re-established after being dropped; events may have been missed"
+            },
+
         InvalidUrl
             { url: Url }
             | e | {
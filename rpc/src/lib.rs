@@ -32,6 +32,7 @@ extern crate std;
 
 mod prelude;
 
+pub mod batch;
 pub mod client;
 
 #[cfg(any(
@@ -41,7 +42,7 @@ pub mod client;
 ))]
 pub use client::{Client, Subscription, SubscriptionClient};
 #[cfg(feature = "http-client")]
-pub use client::{HttpClient, HttpClientUrl};
+pub use client::{FailoverClient, HttpClient, HttpClientUrl};
 #[cfg(feature = "mock-client")]
 pub use client::{MockClient, MockRequestMatcher, MockRequestMethodMatcher};
 #[cfg(feature = "websocket-client")]
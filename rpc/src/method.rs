@@ -44,6 +44,9 @@ pub enum Method {
     /// Broadcast transaction commit
     BroadcastTxCommit,
 
+    /// Check a transaction without adding it to the mempool
+    CheckTx,
+
     /// Get commit info for a block
     Commit,
 
@@ -53,6 +56,15 @@ pub enum Method {
     /// Get consensus state
     ConsensusState,
 
+    /// Get the complete consensus state, including the state of all peers
+    DumpConsensusState,
+
+    /// Dial a list of peers (unsafe)
+    DialPeers,
+
+    /// Dial a list of seeds (unsafe)
+    DialSeeds,
+
     /// Get genesis file
     Genesis,
 
@@ -71,6 +83,9 @@ pub enum Method {
     /// Get network info
     NetInfo,
 
+    /// Get the number of unconfirmed transactions
+    NumUnconfirmedTxs,
+
     /// Get node status
     Status,
 
@@ -80,6 +95,9 @@ pub enum Method {
     /// Search for transactions with their results
     TxSearch,
 
+    /// Get unconfirmed transactions
+    UnconfirmedTxs,
+
     /// Get validator info for a block
     Validators,
 
@@ -91,6 +109,9 @@ pub enum Method {
 
     /// Broadcast evidence
     BroadcastEvidence,
+
+    /// Remove all transactions from the mempool (unsafe)
+    UnsafeFlushMempool,
 }
 
 impl Method {
@@ -108,20 +129,27 @@ impl Method {
             Method::BroadcastTxAsync => "broadcast_tx_async",
             Method::BroadcastTxSync => "broadcast_tx_sync",
             Method::BroadcastTxCommit => "broadcast_tx_commit",
+            Method::CheckTx => "check_tx",
             Method::Commit => "commit",
             Method::ConsensusParams => "consensus_params",
             Method::ConsensusState => "consensus_state",
+            Method::DumpConsensusState => "dump_consensus_state",
+            Method::DialPeers => "dial_peers",
+            Method::DialSeeds => "dial_seeds",
             Method::Genesis => "genesis",
             Method::GenesisChunked => "genesis_chunked",
             Method::Header => "header",
             Method::HeaderByHash => "header_by_hash",
             Method::Health => "health",
             Method::NetInfo => "net_info",
+            Method::NumUnconfirmedTxs => "num_unconfirmed_txs",
             Method::Status => "status",
             Method::Subscribe => "subscribe",
             Method::Tx => "tx",
             Method::TxSearch => "tx_search",
+            Method::UnconfirmedTxs => "unconfirmed_txs",
             Method::Unsubscribe => "unsubscribe",
+            Method::UnsafeFlushMempool => "unsafe_flush_mempool",
             Method::Validators => "validators",
         }
     }
@@ -145,17 +173,24 @@ impl FromStr for Method {
             "broadcast_tx_async" => Method::BroadcastTxAsync,
             "broadcast_tx_sync" => Method::BroadcastTxSync,
             "broadcast_tx_commit" => Method::BroadcastTxCommit,
+            "check_tx" => Method::CheckTx,
             "commit" => Method::Commit,
             "consensus_params" => Method::ConsensusParams,
             "consensus_state" => Method::ConsensusState,
+            "dump_consensus_state" => Method::DumpConsensusState,
+            "dial_peers" => Method::DialPeers,
+            "dial_seeds" => Method::DialSeeds,
             "genesis" => Method::Genesis,
             "health" => Method::Health,
             "net_info" => Method::NetInfo,
+            "num_unconfirmed_txs" => Method::NumUnconfirmedTxs,
             "status" => Method::Status,
             "subscribe" => Method::Subscribe,
             "tx" => Method::Tx,
             "tx_search" => Method::TxSearch,
+            "unconfirmed_txs" => Method::UnconfirmedTxs,
             "unsubscribe" => Method::Unsubscribe,
+            "unsafe_flush_mempool" => Method::UnsafeFlushMempool,
             "validators" => Method::Validators,
             other => return Err(Error::method_not_found(other.to_string())),
         })
@@ -4,8 +4,10 @@
 //!
 //! [`Query`]: struct.Query.html
 
-use core::{fmt, str::FromStr};
+use alloc::collections::BTreeMap;
+use core::{cmp::Ordering, fmt, str::FromStr};
 
+use tendermint::abci;
 use time::{
     format_description::well_known::Rfc3339,
     macros::{format_description, offset},
@@ -169,6 +171,73 @@ impl Query {
         self.conditions.push(Condition::exists(key.to_string()));
         self
     }
+
+    /// Evaluate the query against the given events, as the node does when
+    /// publishing events to subscribers.
+    ///
+    /// The attributes of the events are looked up by composite keys of the
+    /// form `<event type>.<attribute key>`, e.g. `transfer.recipient`. A
+    /// condition holds if any of the values for its key satisfies it, and the
+    /// query matches if all of its conditions hold. The event type of the
+    /// query is looked up under the `tm.event` key, which the node adds to
+    /// the events it publishes, but which is absent from the events returned
+    /// by e.g. the `/block_results` endpoint.
+    ///
+    /// Attributes whose key or value is not valid UTF-8 are ignored.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use tendermint::abci::Event;
+    /// use tendermint_rpc::query::Query;
+    ///
+    /// let events = vec![Event::new(
+    ///     "transfer",
+    ///     [("recipient", "cosmos1abc", true), ("amount", "100uatom", true)],
+    /// )];
+    ///
+    /// let query: Query = "transfer.recipient = 'cosmos1abc' AND transfer.amount > 50"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert!(query.matches(&events));
+    ///
+    /// let query: Query = "transfer.sender EXISTS".parse().unwrap();
+    /// assert!(!query.matches(&events));
+    /// ```
+    pub fn matches(&self, events: &[abci::Event]) -> bool {
+        let mut flattened = BTreeMap::<String, Vec<String>>::new();
+        for event in events {
+            for attr in &event.attributes {
+                if let (Ok(key), Ok(value)) = (attr.key_str(), attr.value_str()) {
+                    flattened
+                        .entry(format!("{}.{}", event.kind, key))
+                        .or_default()
+                        .push(value.to_owned());
+                }
+            }
+        }
+        self.matches_flattened(&flattened)
+    }
+
+    /// Evaluate the query against events that have already been flattened
+    /// into a map from composite keys to values, like
+    /// [`crate::event::Event::events`].
+    ///
+    /// See [`Query::matches`] for details.
+    pub fn matches_flattened(&self, events: &BTreeMap<String, Vec<String>>) -> bool {
+        if let Some(event_type) = &self.event_type {
+            let event_type = event_type.to_string();
+            if !matches!(events.get("tm.event"), Some(values) if values.contains(&event_type)) {
+                return false;
+            }
+        }
+        self.conditions.iter().all(|condition| {
+            matches!(
+                events.get(&condition.key),
+                Some(values) if values.iter().any(|value| condition.operation.matches(value))
+            )
+        })
+    }
 }
 
 impl Default for Query {
@@ -508,6 +577,27 @@ pub enum Operation {
     Exists,
 }
 
+impl Operation {
+    /// Whether a single value for the key satisfies the operation.
+    ///
+    /// Strings can only be tested for equality. Values compared to numbers
+    /// are interpreted by the number they start with, so that e.g. an amount
+    /// of `100uatom` compares as 100.
+    fn matches(&self, value: &str) -> bool {
+        let (op, expected) = match self {
+            Operation::Exists => return true,
+            Operation::Contains(s) => return value.contains(s.as_str()),
+            Operation::Eq(Operand::String(s)) => return value == s,
+            Operation::Eq(op) => (op, &[Ordering::Equal][..]),
+            Operation::Lt(op) => (op, &[Ordering::Less][..]),
+            Operation::Lte(op) => (op, &[Ordering::Less, Ordering::Equal][..]),
+            Operation::Gt(op) => (op, &[Ordering::Greater][..]),
+            Operation::Gte(op) => (op, &[Ordering::Greater, Ordering::Equal][..]),
+        };
+        matches!(op.compare_value(value), Some(ordering) if expected.contains(&ordering))
+    }
+}
+
 /// A typed operand for use in an [`Condition`].
 ///
 /// According to the [Tendermint RPC subscribe docs][tm-subscribe],
@@ -547,6 +637,72 @@ impl fmt::Display for Operand {
     }
 }
 
+impl Operand {
+    /// Compare the given value to this operand, if the value can be
+    /// interpreted as the same type as the operand.
+    fn compare_value(&self, value: &str) -> Option<Ordering> {
+        match self {
+            Operand::String(_) => None,
+            Operand::Signed(i) => Number::extract(value)?.compare(Number::Int(i128::from(*i))),
+            Operand::Unsigned(u) => Number::extract(value)?.compare(Number::Int(i128::from(*u))),
+            Operand::Float(f) => Number::extract(value)?.compare(Number::Float(*f)),
+            Operand::Date(d) => {
+                let date = Date::parse(value, &format_description!("[year]-[month]-[day]"))
+                    .ok()
+                    .or_else(|| {
+                        let dt = OffsetDateTime::parse(value, &Rfc3339).ok()?;
+                        Some(dt.to_offset(offset!(UTC)).date())
+                    })?;
+                Some(date.cmp(d))
+            },
+            Operand::DateTime(dt) => {
+                let value = OffsetDateTime::parse(value, &Rfc3339).ok()?;
+                Some(value.cmp(dt))
+            },
+        }
+    }
+}
+
+/// A number at the start of an event attribute value.
+#[derive(Clone, Copy)]
+enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+impl Number {
+    fn extract(value: &str) -> Option<Self> {
+        let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+
+        let unsigned = value.strip_prefix('-').unwrap_or(value);
+        let int_len = digits(unsigned);
+        if int_len == 0 {
+            return None;
+        }
+        let int_end = value.len() - unsigned.len() + int_len;
+        match unsigned[int_len..].strip_prefix('.').map(digits) {
+            Some(frac_len) if frac_len > 0 => f64::from_str(&value[..int_end + 1 + frac_len])
+                .ok()
+                .map(Number::Float),
+            _ => i128::from_str(&value[..int_end]).ok().map(Number::Int),
+        }
+    }
+
+    fn compare(self, other: Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Int(a), Number::Int(b)) => Some(a.cmp(&b)),
+            (a, b) => a.as_f64().partial_cmp(&b.as_f64()),
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Int(i) => i as f64,
+            Number::Float(f) => f,
+        }
+    }
+}
+
 fn fmt_date(d: Date, mut f: impl fmt::Write) -> fmt::Result {
     write!(f, "{:04}-{:02}-{:02}", d.year(), d.month() as u8, d.day())
 }
@@ -997,4 +1153,106 @@ mod test {
             }
         );
     }
+
+    fn transfer_events() -> Vec<abci::Event> {
+        vec![
+            abci::Event::new(
+                "transfer",
+                [
+                    ("recipient", "cosmos1abc"),
+                    ("sender", "cosmos1def"),
+                    ("amount", "100uatom"),
+                ],
+            ),
+            abci::Event::new("transfer", [("recipient", "cosmos1ghi"), ("amount", "2.5")]),
+            abci::Event::new(
+                "block",
+                [
+                    ("height", "-5"),
+                    ("date", "2024-03-01"),
+                    ("time", "2024-03-01T12:00:00.5Z"),
+                ],
+            ),
+        ]
+    }
+
+    fn matches(query: &str) -> bool {
+        Query::from_str(query).unwrap().matches(&transfer_events())
+    }
+
+    #[test]
+    fn empty_query_matches_anything() {
+        assert!(Query::default().matches(&[]));
+        assert!(Query::default().matches(&transfer_events()));
+    }
+
+    #[test]
+    fn match_composite_keys() {
+        assert!(matches("transfer.recipient = 'cosmos1abc'"));
+        // Any of the values for a key may satisfy the condition.
+        assert!(matches("transfer.recipient = 'cosmos1ghi'"));
+        assert!(!matches("transfer.recipient = 'cosmos1def'"));
+        assert!(matches(
+            "transfer.recipient = 'cosmos1abc' AND transfer.sender = 'cosmos1def'"
+        ));
+        assert!(!matches(
+            "transfer.recipient = 'cosmos1abc' AND transfer.sender = 'cosmos1abc'"
+        ));
+        assert!(!matches("transfer.memo = ''"));
+    }
+
+    #[test]
+    fn match_contains_and_exists() {
+        assert!(matches("transfer.recipient CONTAINS 'ghi'"));
+        assert!(!matches("transfer.recipient CONTAINS 'xyz'"));
+        assert!(matches("transfer.sender EXISTS"));
+        assert!(!matches("transfer.memo EXISTS"));
+        assert!(!matches("sender EXISTS"));
+    }
+
+    #[test]
+    fn match_numbers() {
+        assert!(matches("transfer.amount = 100"));
+        assert!(matches("transfer.amount > 99"));
+        assert!(matches("transfer.amount < 3"));
+        assert!(!matches("transfer.amount > 100"));
+        assert!(matches("transfer.amount >= 100.0"));
+        assert!(matches("transfer.amount <= 2.5"));
+        assert!(!matches("transfer.amount < 2.5"));
+        assert!(matches("block.height = -5"));
+        assert!(matches("block.height < -4"));
+        // Values that don't start with a number never match.
+        assert!(!matches("transfer.recipient > 0"));
+        assert!(!matches("transfer.recipient < 0"));
+    }
+
+    #[test]
+    fn match_dates_and_times() {
+        assert!(matches("block.date = DATE 2024-03-01"));
+        assert!(matches("block.date > DATE 2024-02-29"));
+        assert!(!matches("block.date < DATE 2024-03-01"));
+        assert!(matches("block.time = DATE 2024-03-01"));
+        assert!(matches("block.time > TIME 2024-03-01T12:00:00Z"));
+        assert!(matches("block.time <= TIME 2024-03-01T12:00:00.5Z"));
+        assert!(!matches("block.date > TIME 2024-03-01T12:00:00Z"));
+    }
+
+    #[test]
+    fn strings_only_match_for_equality() {
+        assert!(!matches("transfer.recipient < 'z'"));
+        assert!(!matches("transfer.recipient >= 'cosmos1abc'"));
+    }
+
+    #[test]
+    fn match_event_type() {
+        let mut events = BTreeMap::new();
+        events.insert("tm.event".to_owned(), vec!["Tx".to_owned()]);
+        events.insert("tx.height".to_owned(), vec!["10".to_owned()]);
+
+        let query = Query::from(EventType::Tx).and_eq("tx.height", 10_u64);
+        assert!(query.matches_flattened(&events));
+        assert!(!Query::from(EventType::NewBlock).matches_flattened(&events));
+        // The event type is not part of the events of a block.
+        assert!(!Query::from(EventType::Tx).matches(&transfer_events()));
+    }
 }
@@ -1,4 +1,4 @@
-use tendermint::consensus::params::VersionParams;
+use tendermint::{consensus::params::VersionParams, Block};
 
 use super::*;
 
@@ -122,6 +122,15 @@ fn outgoing_fixtures() {
                     base64::decode("c3luYy1rZXk9dmFsdWU=").unwrap()
                 );
             },
+            "check_tx" => {
+                let wrapped =
+                    serde_json::from_str::<RequestWrapper<endpoint::check_tx::Request>>(&content)
+                        .unwrap();
+                assert_eq!(
+                    wrapped.params().tx,
+                    base64::decode("Y2hlY2sta2V5PXZhbHVl").unwrap()
+                );
+            },
             "commit_at_height_10" => {
                 let wrapped =
                     serde_json::from_str::<RequestWrapper<endpoint::commit::Request>>(&content)
@@ -140,6 +149,10 @@ fn outgoing_fixtures() {
                 RequestWrapper<endpoint::consensus_state::Request>,
             >(&content)
             .is_ok()),
+            "dump_consensus_state" => assert!(serde_json::from_str::<
+                RequestWrapper<endpoint::dump_consensus_state::Request>,
+            >(&content)
+            .is_ok()),
             "genesis" => assert!(serde_json::from_str::<
                 RequestWrapper<endpoint::genesis::Request::<serde_json::Value>>,
             >(&content)
@@ -148,6 +161,10 @@ fn outgoing_fixtures() {
                 RequestWrapper<endpoint::net_info::Request>,
             >(&content)
             .is_ok()),
+            "num_unconfirmed_txs" => assert!(serde_json::from_str::<
+                RequestWrapper<endpoint::num_unconfirmed_txs::Request>,
+            >(&content)
+            .is_ok()),
             "status" => assert!(
                 serde_json::from_str::<RequestWrapper<endpoint::status::Request>>(&content).is_ok()
             ),
@@ -249,6 +266,13 @@ fn outgoing_fixtures() {
                 assert_eq!(wrapped.params().per_page, 10);
                 assert_eq!(wrapped.params().order_by, Order::Ascending);
             },
+            "unconfirmed_txs" => {
+                let wrapped = serde_json::from_str::<
+                    RequestWrapper<endpoint::unconfirmed_txs::Request>,
+                >(&content)
+                .unwrap();
+                assert_eq!(wrapped.params().limit, Some(10));
+            },
             _ => {
                 panic!("cannot parse file name: {file_name}");
             },
@@ -333,6 +357,9 @@ fn incoming_fixtures() {
             },
             "block_at_height_1" => {
                 let result = endpoint::block::v0_38::DialectResponse::from_string(content).unwrap();
+                let block = Block::from(result.block.clone());
+                block.validate_basic().unwrap();
+                assert_eq!(block.hash(), result.block_id.hash);
                 assert!(result.block.data.first().is_none());
                 assert!(result.block.evidence.iter().next().is_none());
                 assert_eq!(result.block.header.app_hash.as_bytes(), [0u8; 8]);
@@ -374,6 +401,9 @@ fn incoming_fixtures() {
             },
             "block_at_height_10" => {
                 let result = endpoint::block::v0_38::DialectResponse::from_string(content).unwrap();
+                let block = Block::from(result.block.clone());
+                block.validate_basic().unwrap();
+                assert_eq!(block.hash(), result.block_id.hash);
                 assert!(result.block.data.first().is_none());
                 assert!(result.block.evidence.iter().next().is_none());
                 assert_eq!(result.block.header.app_hash.as_bytes(), &[0u8; 8]);
@@ -441,6 +471,9 @@ fn incoming_fixtures() {
                     endpoint::block_search::v0_38::DialectResponse::from_string(content).unwrap();
                 assert_eq!(result.total_count as usize, result.blocks.len());
                 for response in result.blocks {
+                    let block = Block::from(response.block.clone());
+                    block.validate_basic().unwrap();
+                    assert_eq!(block.hash(), response.block_id.hash);
                     assert!(response.block.header.height.value() > 1);
                 }
             },
@@ -451,6 +484,10 @@ fn incoming_fixtures() {
 
                 // Test a few selected attributes of the results.
                 for block in result.blocks {
+                    let full_block = Block::from(block.block.clone());
+                    full_block.validate_basic().unwrap();
+                    assert_eq!(full_block.hash(), block.block_id.hash);
+
                     let evidence = block.block.evidence.iter().next().unwrap();
 
                     use tendermint::vote;
@@ -573,6 +610,15 @@ fn incoming_fixtures() {
                 );
                 assert!(result.log.is_empty());
             },
+            "check_tx" => {
+                let result = endpoint::check_tx::Response::from_string(content).unwrap();
+                assert_eq!(result.check_tx.code, abci::Code::Ok);
+                assert!(result.check_tx.data.is_empty());
+                assert!(result.check_tx.events.is_empty());
+                assert_eq!(result.check_tx.gas_wanted, 1);
+                assert_eq!(result.check_tx.gas_used, 0);
+                assert!(result.check_tx.log.is_empty());
+            },
             "commit_at_height_10" => {
                 let result = endpoint::commit::Response::from_string(content).unwrap();
                 assert!(!result.signed_header.commit.block_id.hash.is_empty());
@@ -642,6 +688,16 @@ fn incoming_fixtures() {
             "consensus_state" => {
                 assert!(endpoint::consensus_state::Response::from_string(content).is_ok());
             },
+            "dump_consensus_state" => {
+                let result =
+                    endpoint::dump_consensus_state::Response::from_string(content).unwrap();
+                assert!(result.round_state.height_round_step().is_some());
+                assert!(!result.round_state.validators.validators().is_empty());
+                assert!(!result.peers.is_empty());
+                for peer in result.peers {
+                    assert!(peer.node_address.contains('@'));
+                }
+            },
             "genesis" => {
                 let result =
                     endpoint::genesis::Response::<Option<serde_json::Value>>::from_string(content)
@@ -709,6 +765,12 @@ fn incoming_fixtures() {
                 assert_eq!(result.n_peers, 0);
                 assert!(result.peers.is_empty());
             },
+            "num_unconfirmed_txs" => {
+                let result = endpoint::num_unconfirmed_txs::Response::from_string(content).unwrap();
+                assert_eq!(result.n_txs, 0);
+                assert_eq!(result.total, 0);
+                assert_eq!(result.total_bytes, 0);
+            },
             "status" => {
                 let result = endpoint::status::Response::from_string(content).unwrap();
                 assert_eq!(
@@ -1399,6 +1461,13 @@ fn incoming_fixtures() {
                     assert_ne!(proof.root_hash.as_bytes(), [0; 32]);
                 }
             },
+            "unconfirmed_txs" => {
+                let result = endpoint::unconfirmed_txs::Response::from_string(content).unwrap();
+                assert_eq!(result.n_txs, 0);
+                assert_eq!(result.total, 0);
+                assert_eq!(result.total_bytes, 0);
+                assert!(result.txs.is_empty());
+            },
             _ => {
                 panic!("cannot parse file name: {file_name}");
             },
@@ -140,8 +140,12 @@ tendermint_pb_modules! {
 mod key_conversions {
     use super::{Id, LENGTH};
     use crate::crypto::default::Sha256;
+    #[cfg(feature = "bls12-381")]
+    use crate::public_key::Bls12_381;
     #[cfg(feature = "secp256k1")]
     use crate::public_key::Secp256k1;
+    #[cfg(feature = "sr25519")]
+    use crate::public_key::Sr25519;
     use crate::public_key::{Ed25519, PublicKey};
     use digest::Digest;
 
@@ -167,12 +171,34 @@ mod key_conversions {
         }
     }
 
+    // SHA256(pk)[:20]
+    #[cfg(feature = "bls12-381")]
+    impl From<Bls12_381> for Id {
+        fn from(pk: Bls12_381) -> Id {
+            let digest = Sha256::digest(pk.as_bytes());
+            Id(digest[..LENGTH].try_into().unwrap())
+        }
+    }
+
+    // SHA256(pk)[:20]
+    #[cfg(feature = "sr25519")]
+    impl From<Sr25519> for Id {
+        fn from(pk: Sr25519) -> Id {
+            let digest = Sha256::digest(pk.as_bytes());
+            Id(digest[..LENGTH].try_into().unwrap())
+        }
+    }
+
     impl From<PublicKey> for Id {
         fn from(pub_key: PublicKey) -> Id {
             match pub_key {
                 PublicKey::Ed25519(pk) => Id::from(pk),
                 #[cfg(feature = "secp256k1")]
                 PublicKey::Secp256k1(pk) => Id::from(pk),
+                #[cfg(feature = "bls12-381")]
+                PublicKey::Bls12_381(pk) => Id::from(pk),
+                #[cfg(feature = "sr25519")]
+                PublicKey::Sr25519(pk) => Id::from(pk),
             }
         }
     }
@@ -26,7 +26,7 @@ pub use self::{
     round::*,
     size::Size,
 };
-use crate::{evidence, prelude::*};
+use crate::{crypto::Sha256, evidence, merkle::MerkleHash, prelude::*, tx, Error, Hash};
 
 /// Blocks consist of a header, transactions, votes (the commit), and a list of
 /// evidence of malfeasance (i.e. signing conflicting votes).
@@ -127,4 +127,97 @@ impl Block {
     pub fn last_commit(&self) -> &Option<Commit> {
         &self.last_commit
     }
+
+    /// Computes the hash of this block, i.e. the hash of its header.
+    ///
+    /// Hashes missing from the header are filled in from the block contents
+    /// first, as CometBFT does.
+    #[cfg(feature = "rust-crypto")]
+    pub fn hash(&self) -> Hash {
+        self.hash_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Hash this block with a Merkle hasher provided by a crypto provider.
+    pub fn hash_with<H>(&self) -> Hash
+    where
+        H: MerkleHash + Sha256 + Default,
+    {
+        let mut header = self.header.clone();
+        header
+            .last_commit_hash
+            .get_or_insert_with(|| self.last_commit_hash_with::<H>());
+        header
+            .data_hash
+            .get_or_insert_with(|| tx::data_hash_with::<H>(&self.data));
+        header
+            .evidence_hash
+            .get_or_insert_with(|| self.evidence.hash_with::<H>());
+        header.hash_with::<H>()
+    }
+
+    /// Checks that the header commits to the transactions, the evidence and
+    /// the last commit of this block.
+    ///
+    /// Once the header is verified, e.g. by the light client, this makes the
+    /// whole block trusted.
+    #[cfg(feature = "rust-crypto")]
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        self.validate_basic_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Validate this block with a Merkle hasher provided by a crypto provider.
+    pub fn validate_basic_with<H>(&self) -> Result<(), Error>
+    where
+        H: MerkleHash + Sha256 + Default,
+    {
+        if let Some(last_commit) = &self.last_commit {
+            last_commit
+                .validate_basic()
+                .map_err(|e| Error::invalid_block(format!("wrong last commit: {}", e.detail())))?;
+        }
+        for (i, evidence) in self.evidence.iter().enumerate() {
+            evidence.validate_basic_with::<H>().map_err(|e| {
+                Error::invalid_block(format!("invalid evidence (#{i}): {}", e.detail()))
+            })?;
+        }
+
+        check_header_hash(
+            "LastCommitHash",
+            self.last_commit_hash_with::<H>(),
+            self.header.last_commit_hash,
+        )?;
+        check_header_hash(
+            "DataHash",
+            tx::data_hash_with::<H>(&self.data),
+            self.header.data_hash,
+        )?;
+        check_header_hash(
+            "EvidenceHash",
+            self.evidence.hash_with::<H>(),
+            self.header.evidence_hash,
+        )
+    }
+
+    // The first block has no last commit, which CometBFT hashes as an empty
+    // commit.
+    fn last_commit_hash_with<H>(&self) -> Hash
+    where
+        H: MerkleHash + Default,
+    {
+        match &self.last_commit {
+            Some(last_commit) => last_commit.hash_with::<H>(),
+            None => Commit::default().hash_with::<H>(),
+        }
+    }
+}
+
+fn check_header_hash(field: &str, expected: Hash, actual: Option<Hash>) -> Result<(), Error> {
+    let actual = actual.unwrap_or_default();
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(Error::invalid_block(format!(
+            "wrong Header.{field}: expected {expected}, got {actual}"
+        )))
+    }
 }
@@ -1,11 +1,15 @@
 //! Commits to a Tendermint blockchain
 
+use prost::Message;
 use serde::{Deserialize, Serialize};
-use tendermint_proto::v0_37::types::Commit as RawCommit;
+use tendermint_proto::v0_37::types::{Commit as RawCommit, CommitSig as RawCommitSig};
 
 use crate::{
     block::{commit_sig::CommitSig, Height, Id, Round},
+    merkle::{self, MerkleHash},
     prelude::*,
+    vote::{self, ValidatorIndex, Vote},
+    Error, Hash,
 };
 
 /// Commit contains the justification (ie. a set of signatures) that a block was committed by a set
@@ -71,6 +75,83 @@ tendermint_pb_modules! {
     }
 }
 
+impl Commit {
+    /// Computes the Merkle root of the signatures in this commit, as
+    /// committed to by the `last_commit_hash` of the next block header.
+    #[cfg(feature = "rust-crypto")]
+    pub fn hash(&self) -> Hash {
+        self.hash_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Hash this commit with a Merkle hasher provided by a crypto provider.
+    pub fn hash_with<H>(&self) -> Hash
+    where
+        H: MerkleHash + Default,
+    {
+        let signatures = self
+            .signatures
+            .iter()
+            .map(|sig| RawCommitSig::from(sig.clone()).encode_to_vec())
+            .collect::<Vec<_>>();
+        Hash::Sha256(merkle::simple_hash_from_byte_vectors::<H>(&signatures))
+    }
+
+    /// Reconstructs the precommit of the validator at the given index, or
+    /// returns `None` if the validator did not vote.
+    ///
+    /// The vote is for nil if the validator did not vote for the committed
+    /// block.
+    pub fn vote(&self, validator_index: ValidatorIndex) -> Option<Vote> {
+        let (validator_address, timestamp, signature, block_id) =
+            match self.signatures.get(usize::from(validator_index))? {
+                CommitSig::BlockIdFlagAbsent => return None,
+                CommitSig::BlockIdFlagCommit {
+                    validator_address,
+                    timestamp,
+                    signature,
+                } => (validator_address, timestamp, signature, Some(self.block_id)),
+                CommitSig::BlockIdFlagNil {
+                    validator_address,
+                    timestamp,
+                    signature,
+                } => (validator_address, timestamp, signature, None),
+            };
+        Some(Vote {
+            vote_type: vote::Type::Precommit,
+            height: self.height,
+            round: self.round,
+            block_id,
+            timestamp: Some(*timestamp),
+            validator_address: *validator_address,
+            validator_index,
+            signature: signature.clone(),
+            extension: Default::default(),
+            extension_signature: None,
+        })
+    }
+
+    /// Performs basic validation of the commit, without verifying the
+    /// signatures.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.height.value() >= 1 {
+            if self.block_id == Id::default() {
+                return Err(Error::invalid_block(
+                    "commit cannot be for nil block".to_string(),
+                ));
+            }
+            if self.signatures.is_empty() {
+                return Err(Error::invalid_block("no signatures in commit".to_string()));
+            }
+            for (i, commit_sig) in self.signatures.iter().enumerate() {
+                commit_sig.validate_basic().map_err(|e| {
+                    Error::invalid_block(format!("wrong CommitSig #{i}: {}", e.detail()))
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Default for Commit {
     fn default() -> Self {
         Commit {
@@ -82,3 +163,39 @@ impl Default for Commit {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account, Signature, Time};
+
+    fn commit(signature: Option<Signature>) -> Commit {
+        Commit {
+            height: Height::from(1_u32),
+            round: Round::default(),
+            block_id: Id {
+                hash: Hash::Sha256([1; 32]),
+                part_set_header: Default::default(),
+            },
+            signatures: vec![
+                CommitSig::BlockIdFlagCommit {
+                    validator_address: account::Id::new([1; 20]),
+                    timestamp: Time::unix_epoch(),
+                    signature,
+                },
+                CommitSig::BlockIdFlagAbsent,
+            ],
+        }
+    }
+
+    #[test]
+    fn validates_commit_sigs() {
+        let signature = Signature::try_from(&[2; 64][..]).unwrap();
+        commit(Some(signature)).validate_basic().unwrap();
+    }
+
+    #[test]
+    fn rejects_unsigned_commit_sig() {
+        assert!(commit(None).validate_basic().is_err());
+    }
+}
@@ -2,7 +2,7 @@
 
 use tendermint_proto::google::protobuf::Timestamp;
 
-use crate::{account, prelude::*, Signature, Time};
+use crate::{account, prelude::*, Error, Signature, Time};
 
 /// The special zero timestamp is to be used for absent votes,
 /// where there is no timestamp to speak of.
@@ -78,6 +78,24 @@ impl CommitSig {
     pub fn is_nil(&self) -> bool {
         matches!(self, Self::BlockIdFlagNil { .. })
     }
+
+    /// Performs basic validation of this signature, as CometBFT does.
+    ///
+    /// Absent signatures carry no validator address, timestamp or signature,
+    /// and the validator addresses and signatures of the others have the
+    /// expected length, which is checked when decoding them. This checks
+    /// that the votes which were received are signed.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        match self {
+            Self::BlockIdFlagAbsent => Ok(()),
+            Self::BlockIdFlagCommit { signature, .. } | Self::BlockIdFlagNil { signature, .. } => {
+                signature
+                    .as_ref()
+                    .map(|_| ())
+                    .ok_or_else(Error::empty_signature)
+            },
+        }
+    }
 }
 
 tendermint_pb_modules! {
@@ -91,6 +109,10 @@ tendermint_pb_modules! {
 
         fn try_from(value: RawCommitSig) -> Result<Self, Self::Error> {
             if value.block_id_flag == BlockIdFlag::Absent as i32 {
+                if !value.validator_address.is_empty() {
+                    return Err(Error::invalid_validator_address());
+                }
+
                 if value.timestamp.is_some() {
                     let timestamp = value.timestamp.unwrap();
                     // 0001-01-01T00:00:00.000Z translates to EPOCH-62135596800 seconds
@@ -205,4 +227,72 @@ tendermint_pb_modules! {
         let commit_sig = CommitSig::try_from(raw_commit_sg).unwrap();
         assert_eq!(commit_sig, CommitSig::BlockIdFlagAbsent);
     }
+
+    #[cfg(test)]
+    fn raw_commit_sig(block_id_flag: BlockIdFlag) -> RawCommitSig {
+        RawCommitSig {
+            block_id_flag: block_id_flag as i32,
+            validator_address: vec![1; 20],
+            timestamp: Some(crate::Time::unix_epoch().into()),
+            signature: vec![2; 64],
+        }
+    }
+
+    #[test]
+    #[cfg(test)]
+    fn absent_commit_sig_has_no_validator_address() {
+        let raw = RawCommitSig {
+            timestamp: Some(ZERO_TIMESTAMP),
+            signature: vec![],
+            ..raw_commit_sig(BlockIdFlag::Absent)
+        };
+        assert!(CommitSig::try_from(raw).is_err());
+    }
+
+    #[test]
+    #[cfg(test)]
+    fn absent_commit_sig_has_no_timestamp() {
+        let raw = RawCommitSig {
+            validator_address: vec![],
+            signature: vec![],
+            ..raw_commit_sig(BlockIdFlag::Absent)
+        };
+        assert!(CommitSig::try_from(raw).is_err());
+    }
+
+    #[test]
+    #[cfg(test)]
+    fn absent_commit_sig_has_no_signature() {
+        let raw = RawCommitSig {
+            validator_address: vec![],
+            timestamp: Some(ZERO_TIMESTAMP),
+            ..raw_commit_sig(BlockIdFlag::Absent)
+        };
+        assert!(CommitSig::try_from(raw).is_err());
+    }
+
+    #[test]
+    #[cfg(test)]
+    fn commit_sig_has_a_20_byte_validator_address() {
+        for flag in [BlockIdFlag::Commit, BlockIdFlag::Nil] {
+            CommitSig::try_from(raw_commit_sig(flag)).unwrap();
+            let raw = RawCommitSig {
+                validator_address: vec![1; 19],
+                ..raw_commit_sig(flag)
+            };
+            assert!(CommitSig::try_from(raw).is_err());
+        }
+    }
+
+    #[test]
+    #[cfg(test)]
+    fn commit_sig_has_a_signature_of_the_right_length() {
+        for flag in [BlockIdFlag::Commit, BlockIdFlag::Nil] {
+            let raw = RawCommitSig {
+                signature: vec![2; 63],
+                ..raw_commit_sig(flag)
+            };
+            assert!(CommitSig::try_from(raw).is_err());
+        }
+    }
 }
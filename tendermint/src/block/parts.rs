@@ -1,9 +1,22 @@
 //! Block parts
 
 use serde::{Deserialize, Serialize};
-use tendermint_proto::v0_37::types::PartSetHeader as RawPartSetHeader;
+use tendermint_proto::v0_37::types::{Block as RawBlock, PartSetHeader as RawPartSetHeader};
+use tendermint_proto::Protobuf;
 
-use crate::{error::Error, prelude::*, Hash};
+use crate::{
+    block::{Block, Size},
+    error::Error,
+    merkle::{self, MerkleHash},
+    prelude::*,
+    Hash,
+};
+
+/// Maximum size of a block part in bytes.
+pub const BLOCK_PART_SIZE_BYTES: usize = 65536;
+
+/// Maximum number of parts of a block of at most [`Size::MAX_BYTES`].
+pub const MAX_BLOCK_PARTS_COUNT: u32 = (Size::MAX_BYTES / BLOCK_PART_SIZE_BYTES as u64) as u32 + 1;
 
 /// Block parts header
 #[derive(
@@ -19,9 +32,24 @@ pub struct Header {
     pub hash: Hash,
 }
 
+/// A part of a serialized block, along with the proof of its inclusion in
+/// the [`PartSet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Part {
+    /// Index of the part
+    pub index: u32,
+
+    /// Contents of the part
+    pub bytes: Vec<u8>,
+
+    /// Merkle proof of the part against the part set hash
+    pub proof: merkle::Proof,
+}
+
 tendermint_pb_modules! {
     use pb::types::{
-        CanonicalPartSetHeader as RawCanonicalPartSetHeader, PartSetHeader as RawPartSetHeader,
+        CanonicalPartSetHeader as RawCanonicalPartSetHeader, Part as RawPart,
+        PartSetHeader as RawPartSetHeader,
     };
     use crate::{
         error::Error,
@@ -29,7 +57,7 @@ tendermint_pb_modules! {
         prelude::*,
         Hash,
     };
-    use super::Header;
+    use super::{Header, Part};
 
     impl Protobuf<RawPartSetHeader> for Header {}
 
@@ -56,6 +84,30 @@ tendermint_pb_modules! {
         }
     }
 
+    impl Protobuf<RawPart> for Part {}
+
+    impl TryFrom<RawPart> for Part {
+        type Error = Error;
+
+        fn try_from(value: RawPart) -> Result<Self, Self::Error> {
+            Ok(Self {
+                index: value.index,
+                bytes: value.bytes,
+                proof: value.proof.ok_or_else(Error::missing_data)?.try_into()?,
+            })
+        }
+    }
+
+    impl From<Part> for RawPart {
+        fn from(value: Part) -> Self {
+            RawPart {
+                index: value.index,
+                bytes: value.bytes,
+                proof: Some(value.proof.into()),
+            }
+        }
+    }
+
     impl TryFrom<RawCanonicalPartSetHeader> for Header {
         type Error = Error;
 
@@ -96,3 +148,297 @@ impl Header {
         Ok(Header { total, hash })
     }
 }
+
+impl Part {
+    /// Performs basic validation of the part, without checking the proof.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.bytes.len() > BLOCK_PART_SIZE_BYTES {
+            return Err(Error::invalid_part(format!(
+                "too big: {} bytes, max: {}",
+                self.bytes.len(),
+                BLOCK_PART_SIZE_BYTES
+            )));
+        }
+        if self.proof.index != u64::from(self.index) {
+            return Err(Error::invalid_part(format!(
+                "proof index {} does not match part index {}",
+                self.proof.index, self.index
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A serialized block split into parts, for gossiping the block during
+/// consensus.
+///
+/// The part set is identified by its [`Header`], which commits to the parts
+/// with a Merkle root. A part set is either built from the complete data or
+/// assembled part by part from a header, verifying each part as it arrives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartSet {
+    header: Header,
+    parts: Vec<Option<Part>>,
+    count: u32,
+}
+
+impl PartSet {
+    /// Splits a block into parts of [`BLOCK_PART_SIZE_BYTES`].
+    #[cfg(feature = "rust-crypto")]
+    pub fn from_block(block: &Block) -> Self {
+        Self::from_block_with::<crate::crypto::default::Sha256>(block)
+    }
+
+    /// Splits a block into parts, with a Merkle hasher provided by a crypto
+    /// provider.
+    pub fn from_block_with<H>(block: &Block) -> Self
+    where
+        H: MerkleHash + Default,
+    {
+        let data = Protobuf::<RawBlock>::encode_vec(block.clone());
+        Self::from_data_with::<H>(&data, BLOCK_PART_SIZE_BYTES)
+    }
+
+    /// Splits arbitrary data into parts of at most `part_size` bytes.
+    #[cfg(feature = "rust-crypto")]
+    pub fn from_data(data: &[u8], part_size: usize) -> Self {
+        Self::from_data_with::<crate::crypto::default::Sha256>(data, part_size)
+    }
+
+    /// Splits arbitrary data into parts, with a Merkle hasher provided by a
+    /// crypto provider.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `part_size` is zero.
+    pub fn from_data_with<H>(data: &[u8], part_size: usize) -> Self
+    where
+        H: MerkleHash + Default,
+    {
+        let chunks = data.chunks(part_size).collect::<Vec<_>>();
+        let (root, proofs) = merkle::proofs_from_byte_vectors::<H>(&chunks);
+        let parts = chunks
+            .into_iter()
+            .zip(proofs)
+            .zip(0..)
+            .map(|((bytes, proof), index)| {
+                Some(Part {
+                    index,
+                    bytes: bytes.to_vec(),
+                    proof,
+                })
+            })
+            .collect::<Vec<_>>();
+        let count = parts.len() as u32;
+        Self {
+            header: Header {
+                total: count,
+                hash: Hash::Sha256(root),
+            },
+            parts,
+            count,
+        }
+    }
+
+    /// Creates an empty part set to be filled with parts matching the
+    /// given header.
+    ///
+    /// Fails if the header has no parts, or more than
+    /// [`MAX_BLOCK_PARTS_COUNT`].
+    pub fn from_header(header: Header) -> Result<Self, Error> {
+        if header.total == 0 {
+            return Err(Error::invalid_part_set_header("zero total".to_string()));
+        }
+        if header.total > MAX_BLOCK_PARTS_COUNT {
+            return Err(Error::invalid_part_set_header(format!(
+                "total {} exceeds maximum {}",
+                header.total, MAX_BLOCK_PARTS_COUNT
+            )));
+        }
+        Ok(Self {
+            header,
+            parts: vec![None; header.total as usize],
+            count: 0,
+        })
+    }
+
+    /// The header identifying this part set.
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    /// Total number of parts.
+    pub fn total(&self) -> u32 {
+        self.header.total
+    }
+
+    /// Number of parts received so far.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Whether all the parts have been received.
+    pub fn is_complete(&self) -> bool {
+        self.count == self.header.total
+    }
+
+    /// Get the part with the given index, if it has been received.
+    pub fn part(&self, index: u32) -> Option<&Part> {
+        self.parts.get(index as usize)?.as_ref()
+    }
+
+    /// Adds a part, after checking its proof against the part set hash.
+    ///
+    /// Returns `Ok(false)` if the part had already been added.
+    #[cfg(feature = "rust-crypto")]
+    pub fn add_part(&mut self, part: Part) -> Result<bool, Error> {
+        self.add_part_with::<crate::crypto::default::Sha256>(part)
+    }
+
+    /// Adds a part, with a Merkle hasher provided by a crypto provider.
+    pub fn add_part_with<H>(&mut self, part: Part) -> Result<bool, Error>
+    where
+        H: MerkleHash + Default,
+    {
+        let slot = self.parts.get_mut(part.index as usize).ok_or_else(|| {
+            Error::invalid_part(format!(
+                "unexpected index {} in a set of {} parts",
+                part.index, self.header.total
+            ))
+        })?;
+        if slot.is_some() {
+            return Ok(false);
+        }
+
+        part.validate_basic()?;
+        // The proof should be compatible with the number of parts.
+        if part.proof.total != u64::from(self.header.total) {
+            return Err(Error::invalid_part(format!(
+                "proof is for a set of {} parts, expected {}",
+                part.proof.total, self.header.total
+            )));
+        }
+        part.proof
+            .verify_with::<H>(&self.header.hash, &part.bytes)
+            .map_err(|e| Error::invalid_part(format!("invalid proof: {}", e.detail())))?;
+
+        *slot = Some(part);
+        self.count += 1;
+        Ok(true)
+    }
+
+    /// The concatenated contents of the parts, if the set is complete.
+    pub fn data(&self) -> Option<Vec<u8>> {
+        if !self.is_complete() {
+            return None;
+        }
+        let parts = self.parts.iter().flatten();
+        let mut data = Vec::with_capacity(parts.clone().map(|part| part.bytes.len()).sum());
+        for part in parts {
+            data.extend_from_slice(&part.bytes);
+        }
+        Some(data)
+    }
+
+    /// Decodes the block from a complete part set.
+    pub fn block(&self) -> Result<Block, Error> {
+        let data = self
+            .data()
+            .ok_or_else(|| Error::incomplete_part_set(self.count, self.header.total))?;
+        Protobuf::<RawBlock>::decode_vec(&data)
+            .map_err(|e| Error::invalid_block(format!("decoding block from parts: {e}")))
+    }
+}
+
+#[cfg(all(test, feature = "rust-crypto"))]
+mod tests {
+    use super::*;
+    use crate::block::Header as BlockHeader;
+
+    fn test_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn split_and_reassemble() {
+        let data = test_data(3500);
+        let source = PartSet::from_data(&data, 1000);
+        assert_eq!(source.total(), 4);
+        assert!(source.is_complete());
+        assert_eq!(source.data().unwrap(), data);
+
+        let mut dest = PartSet::from_header(source.header()).unwrap();
+        assert!(!dest.is_complete());
+        assert!(dest.data().is_none());
+
+        for index in (0..source.total()).rev() {
+            let part = source.part(index).unwrap().clone();
+            assert!(dest.add_part(part.clone()).unwrap());
+            // Adding the same part again is a no-op.
+            assert!(!dest.add_part(part).unwrap());
+        }
+        assert!(dest.is_complete());
+        assert_eq!(dest.data().unwrap(), data);
+        assert_eq!(dest, source);
+    }
+
+    #[test]
+    fn reject_invalid_parts() {
+        let source = PartSet::from_data(&test_data(3500), 1000);
+        let mut dest = PartSet::from_header(source.header()).unwrap();
+
+        // Out of range index.
+        let mut part = source.part(3).unwrap().clone();
+        part.index = 4;
+        assert!(dest.add_part(part).is_err());
+
+        // Tampered contents.
+        let mut part = source.part(0).unwrap().clone();
+        part.bytes[0] ^= 1;
+        assert!(dest.add_part(part).is_err());
+
+        // Proof for another part.
+        let mut part = source.part(0).unwrap().clone();
+        part.proof = source.part(1).unwrap().proof.clone();
+        assert!(dest.add_part(part).is_err());
+
+        // Part from another part set.
+        let other = PartSet::from_data(&test_data(4000), 1000);
+        assert!(dest.add_part(other.part(0).unwrap().clone()).is_err());
+
+        assert_eq!(dest.count(), 0);
+    }
+
+    #[test]
+    fn reject_invalid_headers() {
+        let hash = PartSet::from_data(&test_data(10), 10).header().hash;
+        assert!(PartSet::from_header(Header::new(0, Hash::None).unwrap()).is_err());
+        assert!(
+            PartSet::from_header(Header::new(MAX_BLOCK_PARTS_COUNT + 1, hash).unwrap()).is_err()
+        );
+        assert!(PartSet::from_header(Header::new(u32::MAX, hash).unwrap()).is_err());
+
+        let set = PartSet::from_header(Header::new(MAX_BLOCK_PARTS_COUNT, hash).unwrap()).unwrap();
+        assert_eq!(set.total(), MAX_BLOCK_PARTS_COUNT);
+    }
+
+    #[test]
+    fn block_round_trip() {
+        let header: BlockHeader = serde_json::from_str(include_str!(
+            "../../tests/support/serialization/block/header.json"
+        ))
+        .unwrap();
+        let txs = (0..3).map(|i| test_data(40_000 + i)).collect::<Vec<_>>();
+        let block = Block::new(header, txs, Default::default(), None);
+
+        let source = PartSet::from_block(&block);
+        assert_eq!(source.total(), 2);
+
+        let mut dest = PartSet::from_header(source.header()).unwrap();
+        assert!(dest.block().is_err());
+        for index in 0..source.total() {
+            dest.add_part(source.part(index).unwrap().clone()).unwrap();
+        }
+        assert_eq!(dest.block().unwrap(), block);
+    }
+}
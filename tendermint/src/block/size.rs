@@ -16,11 +16,25 @@ pub struct Size {
     pub max_gas: i64,
 
     /// This parameter has no value anymore in Tendermint-core
-    #[serde(with = "serializers::from_str", default = "Size::default_time_iota_ms")]
+    ///
+    /// It is omitted from the JSON encoding when it has the default value,
+    /// as CometBFT 0.37 and later no longer have it.
+    #[serde(
+        with = "serializers::from_str",
+        default = "Size::default_time_iota_ms",
+        skip_serializing_if = "Size::is_default_time_iota_ms"
+    )]
     pub time_iota_ms: i64,
 }
 
 impl Size {
+    /// The maximum size of a block, in bytes.
+    pub const MAX_BYTES: u64 = 104_857_600;
+
+    fn is_default_time_iota_ms(time_iota_ms: &i64) -> bool {
+        *time_iota_ms == Self::default_time_iota_ms()
+    }
+
     /// The default value for the `time_iota_ms` parameter.
     pub const fn default_time_iota_ms() -> i64 {
         1000
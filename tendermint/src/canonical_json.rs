@@ -0,0 +1,169 @@
+//! Canonical JSON encoding, as used to hash and sign configuration artifacts
+//! such as genesis files and validator keys.
+//!
+//! The output is compact, with object keys sorted in byte order, and strings
+//! escaped as done by Go's `encoding/json`, which CometBFT uses to produce
+//! its JSON. Values are otherwise encoded by their serde implementation,
+//! which for the types of this crate follows the conventions of CometBFT's
+//! `libs/json`: 64-bit integers are encoded as strings, and keys as objects
+//! holding a `type` and a `value`.
+//!
+//! Building Amino-JSON sign documents, such as the Cosmos SDK's `StdSignDoc`,
+//! is out of scope: these are application-level types, defined outside of
+//! CometBFT. [`to_vec`] produces the sorted, compact encoding those
+//! documents are signed over.
+
+use core::fmt::Write as _;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::prelude::*;
+
+/// Serialize the given value as canonical JSON.
+pub fn to_string<T>(value: &T) -> Result<String, serde_json::Error>
+where
+    T: Serialize + ?Sized,
+{
+    let value = serde_json::to_value(value)?;
+    let mut out = String::new();
+    write_value(&value, &mut out);
+    Ok(out)
+}
+
+/// Serialize the given value as canonical JSON bytes.
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, serde_json::Error>
+where
+    T: Serialize + ?Sized,
+{
+    to_string(value).map(String::into_bytes)
+}
+
+fn write_value(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write!(out, "{n}").expect("writing to a string never fails"),
+        Value::String(s) => write_string(s, out),
+        Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(value, out);
+            }
+            out.push(']');
+        },
+        Value::Object(map) => {
+            // The map may preserve insertion order, depending on the features
+            // of `serde_json` enabled in the dependency graph.
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(key, out);
+                out.push(':');
+                write_value(value, out);
+            }
+            out.push('}');
+        },
+    }
+}
+
+/// Write a JSON string, escaping the same characters as Go's
+/// `encoding/json`, including the HTML special characters.
+fn write_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            '<' | '>' | '&' | '\u{2028}' | '\u{2029}' | '\u{00}'..='\u{1f}' => {
+                write!(out, "\\u{:04x}", c as u32).expect("writing to a string never fails")
+            },
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sorts_keys_and_compacts() {
+        let value = json!({
+            "b": [1, {"z": null, "a": true}],
+            "a": "x",
+            "B": -2,
+        });
+        assert_eq!(
+            to_string(&value).unwrap(),
+            r#"{"B":-2,"a":"x","b":[1,{"a":true,"z":null}]}"#
+        );
+    }
+
+    #[test]
+    fn escapes_strings_like_go() {
+        let value = json!("<a href=\"x\">&</a>\\\n\t\u{1}\u{2028}é");
+        assert_eq!(
+            to_string(&value).unwrap(),
+            r#""\u003ca href=\"x\"\u003e\u0026\u003c/a\u003e\\\n\t\u0001\u2028é""#
+        );
+    }
+
+    /// Check that decoding the given JSON, as produced by CometBFT, and
+    /// encoding it back yields the canonical form of the original.
+    fn assert_matches_go<T>(fixture: &str)
+    where
+        T: Serialize + serde::de::DeserializeOwned,
+    {
+        let value: T = serde_json::from_str(fixture).unwrap();
+        let expected: Value = serde_json::from_str(fixture).unwrap();
+        assert_eq!(to_string(&value).unwrap(), to_string(&expected).unwrap());
+    }
+
+    #[test]
+    fn golden_genesis() {
+        assert_matches_go::<crate::Genesis>(include_str!(
+            "../tests/support/canonical_json/genesis_gaia.json"
+        ));
+
+        // These genesis files have no app state, which `Genesis` requires.
+        for fixture in [
+            include_str!("../tests/support/canonical_json/genesis_v0_34.json"),
+            include_str!("../tests/support/canonical_json/genesis_v0_37.json"),
+            include_str!("../tests/support/canonical_json/genesis_v0_38.json"),
+        ] {
+            let genesis: Value = serde_json::from_str(fixture).unwrap();
+            assert_matches_go::<crate::consensus::Params>(&genesis["consensus_params"].to_string());
+            assert_matches_go::<Vec<crate::validator::Info>>(&genesis["validators"].to_string());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rust-crypto")]
+    fn golden_priv_validator_key() {
+        #[derive(Serialize, serde::Deserialize)]
+        struct PrivValidatorKey {
+            address: crate::account::Id,
+            pub_key: crate::PublicKey,
+            priv_key: crate::PrivateKey,
+        }
+
+        assert_matches_go::<PrivValidatorKey>(include_str!(
+            "../tests/support/canonical_json/priv_validator_key.json"
+        ));
+    }
+}
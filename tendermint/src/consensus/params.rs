@@ -3,7 +3,14 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    block, evidence, prelude::*, public_key, serializers::allow_empty_object::allow_empty_object,
+    block,
+    crypto::Sha256,
+    error::Error,
+    evidence,
+    hash::Hash,
+    prelude::*,
+    public_key,
+    serializers::allow_empty_object::{allow_empty_object, serialize_none_as_empty_object},
 };
 
 /// All consensus-relevant parameters that can be adjusted by the ABCI app.
@@ -18,17 +25,177 @@ pub struct Params {
     /// Parameters limiting the types of public keys validators can use.
     pub validator: ValidatorParams,
     /// The ABCI application version. Will default to None if not present.
-    #[serde(default, deserialize_with = "allow_empty_object")]
-    //#[serde(skip)]
+    #[serde(
+        default,
+        deserialize_with = "allow_empty_object",
+        serialize_with = "serialize_none_as_empty_object"
+    )]
     pub version: Option<VersionParams>,
     /// Parameters specific to the Application Blockchain Interface.
     ///
     /// This field has been added in CometBFT 0.38 and will be ignored when
-    /// encoding into earlier protocol versions.
-    #[serde(default)]
+    /// encoding into earlier protocol versions. It is omitted from the JSON
+    /// encoding when unset, as in the genesis files of earlier versions.
+    #[serde(default, skip_serializing_if = "AbciParams::is_empty")]
     pub abci: AbciParams,
 }
 
+/// A partial update of the consensus parameters, as returned by the ABCI
+/// application, where only the groups of parameters that are set change.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ParamsUpdate {
+    /// New block size parameters.
+    pub block: Option<block::Size>,
+    /// New evidence parameters.
+    pub evidence: Option<evidence::Params>,
+    /// New validator parameters.
+    pub validator: Option<ValidatorParams>,
+    /// New version parameters.
+    pub version: Option<VersionParams>,
+    /// New ABCI parameters.
+    pub abci: Option<AbciParams>,
+}
+
+impl From<Params> for ParamsUpdate {
+    fn from(value: Params) -> Self {
+        Self {
+            block: Some(value.block),
+            evidence: Some(value.evidence),
+            validator: Some(value.validator),
+            version: value.version,
+            abci: Some(value.abci),
+        }
+    }
+}
+
+impl Params {
+    /// Check that the parameters are within the bounds accepted by CometBFT.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        let invalid = |reason: String| Err(Error::invalid_consensus_params(reason));
+
+        if self.block.max_bytes == 0 {
+            return invalid("block.max_bytes must be greater than 0".into());
+        }
+        if self.block.max_bytes > block::Size::MAX_BYTES {
+            return invalid(format!(
+                "block.max_bytes is too big: {} > {}",
+                self.block.max_bytes,
+                block::Size::MAX_BYTES
+            ));
+        }
+        if self.block.max_gas < -1 {
+            return invalid(format!(
+                "block.max_gas must be greater or equal to -1, got {}",
+                self.block.max_gas
+            ));
+        }
+        if self.evidence.max_age_num_blocks == 0 {
+            return invalid("evidence.max_age_num_blocks must be greater than 0".into());
+        }
+        if self.evidence.max_age_duration.0.is_zero() {
+            return invalid("evidence.max_age_duration must be greater than 0".into());
+        }
+        if self.evidence.max_bytes < 0 {
+            return invalid(format!(
+                "evidence.max_bytes must be non-negative, got {}",
+                self.evidence.max_bytes
+            ));
+        }
+        // The bound on the block size makes the conversion lossless.
+        if self.evidence.max_bytes > self.block.max_bytes as i64 {
+            return invalid(format!(
+                "evidence.max_bytes is greater than block.max_bytes: {} > {}",
+                self.evidence.max_bytes, self.block.max_bytes
+            ));
+        }
+        if self.validator.pub_key_types.is_empty() {
+            return invalid("validator.pub_key_types must not be empty".into());
+        }
+        Ok(())
+    }
+
+    /// Return the parameters resulting from applying the given update.
+    ///
+    /// The result should be checked with [`Params::validate_basic`].
+    pub fn update(&self, update: &ParamsUpdate) -> Params {
+        let mut params = self.clone();
+        if let Some(block) = &update.block {
+            params.block.max_bytes = block.max_bytes;
+            params.block.max_gas = block.max_gas;
+        }
+        if let Some(evidence) = &update.evidence {
+            params.evidence = evidence.clone();
+        }
+        if let Some(validator) = &update.validator {
+            params.validator = validator.clone();
+        }
+        if let Some(version) = &update.version {
+            params.version = Some(version.clone());
+        }
+        if let Some(abci) = &update.abci {
+            params.abci = abci.clone();
+        }
+        params
+    }
+
+    /// Check that the given update may be applied to these parameters at the
+    /// given height.
+    ///
+    /// Vote extensions cannot be enabled at a past height, nor can their
+    /// enable height be changed once they have been enabled.
+    pub fn validate_update(
+        &self,
+        update: &ParamsUpdate,
+        height: block::Height,
+    ) -> Result<(), Error> {
+        let invalid = |reason: String| Err(Error::invalid_consensus_params(reason));
+
+        let Some(abci) = &update.abci else {
+            return Ok(());
+        };
+        let current = self.abci.enable_height();
+        let updated = abci.enable_height();
+        if current == updated {
+            return Ok(());
+        }
+        if self.abci.vote_extensions_enabled(height) {
+            return invalid(format!(
+                "abci.vote_extensions_enable_height cannot be modified once vote extensions \
+                 are enabled, enable height: {current}, current height: {height}"
+            ));
+        }
+        if updated != 0 && updated <= height.value() {
+            return invalid(format!(
+                "abci.vote_extensions_enable_height cannot be updated to a past height, \
+                 update: {updated}, current height: {height}"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Compute the hash of the parameters which is included in the block
+    /// header as `consensus_hash`.
+    ///
+    /// Only the block size parameters are hashed.
+    #[cfg(feature = "rust-crypto")]
+    pub fn hash(&self) -> Hash {
+        self.hash_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Hash the parameters with a hasher provided by a crypto provider.
+    pub fn hash_with<H: Sha256>(&self) -> Hash {
+        use prost::Message;
+        use tendermint_proto::v0_37::types::HashedParams;
+
+        let hashed = HashedParams {
+            // The bound checked by `validate_basic` makes this lossless.
+            block_max_bytes: self.block.max_bytes as i64,
+            block_max_gas: self.block.max_gas,
+        };
+        Hash::Sha256(H::digest(hashed.encode_to_vec()))
+    }
+}
+
 /// ValidatorParams restrict the public key types validators can use.
 ///
 /// [Tendermint documentation](https://docs.tendermint.com/master/spec/core/data_structures.html#validatorparams)
@@ -60,21 +227,28 @@ pub struct AbciParams {
     pub vote_extensions_enable_height: Option<block::Height>,
 }
 
-// =============================================================================
-// Protobuf conversions
-// =============================================================================
+impl AbciParams {
+    /// Return whether vote extensions are enabled at the given height.
+    pub fn vote_extensions_enabled(&self, height: block::Height) -> bool {
+        let enable_height = self.enable_height();
+        enable_height != 0 && height.value() >= enable_height
+    }
 
-// Todo: How are these key types created?
-fn key_type(s: &str) -> public_key::Algorithm {
-    if s == "Ed25519" || s == "ed25519" {
-        return public_key::Algorithm::Ed25519;
+    /// The enable height of vote extensions, where 0 means disabled.
+    fn enable_height(&self) -> u64 {
+        self.vote_extensions_enable_height
+            .map_or(0, |height| height.value())
     }
-    if s == "Secp256k1" || s == "secp256k1" {
-        return public_key::Algorithm::Secp256k1;
+
+    fn is_empty(&self) -> bool {
+        self.vote_extensions_enable_height.is_none()
     }
-    public_key::Algorithm::Ed25519 // Todo: Shall we error out for invalid key types?
 }
 
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
 mod v0_34 {
     use tendermint_proto::v0_34::{
         abci::ConsensusParams as RawAbciConsensusParams,
@@ -85,7 +259,7 @@ mod v0_34 {
     };
     use tendermint_proto::Protobuf;
 
-    use super::{key_type, Params, ValidatorParams, VersionParams};
+    use super::{Params, ParamsUpdate, ValidatorParams, VersionParams};
     use crate::{error::Error, prelude::*, public_key};
 
     impl Protobuf<RawParams> for Params {}
@@ -160,6 +334,56 @@ mod v0_34 {
         }
     }
 
+    impl TryFrom<RawParams> for ParamsUpdate {
+        type Error = Error;
+
+        fn try_from(value: RawParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                block: value.block.map(TryFrom::try_from).transpose()?,
+                evidence: value.evidence.map(TryFrom::try_from).transpose()?,
+                validator: value.validator.map(TryFrom::try_from).transpose()?,
+                version: value.version.map(TryFrom::try_from).transpose()?,
+                abci: None,
+            })
+        }
+    }
+
+    impl From<ParamsUpdate> for RawParams {
+        fn from(value: ParamsUpdate) -> Self {
+            RawParams {
+                block: value.block.map(From::from),
+                evidence: value.evidence.map(From::from),
+                validator: value.validator.map(From::from),
+                version: value.version.map(From::from),
+            }
+        }
+    }
+
+    impl TryFrom<RawAbciConsensusParams> for ParamsUpdate {
+        type Error = Error;
+
+        fn try_from(value: RawAbciConsensusParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                block: value.block.map(TryFrom::try_from).transpose()?,
+                evidence: value.evidence.map(TryFrom::try_from).transpose()?,
+                validator: value.validator.map(TryFrom::try_from).transpose()?,
+                version: value.version.map(TryFrom::try_from).transpose()?,
+                abci: None,
+            })
+        }
+    }
+
+    impl From<ParamsUpdate> for RawAbciConsensusParams {
+        fn from(value: ParamsUpdate) -> Self {
+            RawAbciConsensusParams {
+                block: value.block.map(From::from),
+                evidence: value.evidence.map(From::from),
+                validator: value.validator.map(From::from),
+                version: value.version.map(From::from),
+            }
+        }
+    }
+
     impl Protobuf<RawValidatorParams> for ValidatorParams {}
 
     impl TryFrom<RawValidatorParams> for ValidatorParams {
@@ -167,7 +391,11 @@ mod v0_34 {
 
         fn try_from(value: RawValidatorParams) -> Result<Self, Self::Error> {
             Ok(Self {
-                pub_key_types: value.pub_key_types.iter().map(|f| key_type(f)).collect(),
+                pub_key_types: value
+                    .pub_key_types
+                    .iter()
+                    .map(|s| public_key::Algorithm::try_from(s.as_str()))
+                    .collect::<Result<_, _>>()?,
             })
         }
     }
@@ -181,6 +409,8 @@ mod v0_34 {
                     .map(|k| match k {
                         public_key::Algorithm::Ed25519 => "ed25519".to_string(),
                         public_key::Algorithm::Secp256k1 => "secp256k1".to_string(),
+                        public_key::Algorithm::Bls12_381 => "bls12_381".to_string(),
+                        public_key::Algorithm::Sr25519 => "sr25519".to_string(),
                     })
                     .collect(),
             }
@@ -215,7 +445,7 @@ mod v0_37 {
     };
     use tendermint_proto::Protobuf;
 
-    use super::{key_type, Params, ValidatorParams, VersionParams};
+    use super::{Params, ParamsUpdate, ValidatorParams, VersionParams};
     use crate::{error::Error, prelude::*, public_key};
 
     impl Protobuf<RawParams> for Params {}
@@ -254,6 +484,31 @@ mod v0_37 {
         }
     }
 
+    impl TryFrom<RawParams> for ParamsUpdate {
+        type Error = Error;
+
+        fn try_from(value: RawParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                block: value.block.map(TryFrom::try_from).transpose()?,
+                evidence: value.evidence.map(TryFrom::try_from).transpose()?,
+                validator: value.validator.map(TryFrom::try_from).transpose()?,
+                version: value.version.map(TryFrom::try_from).transpose()?,
+                abci: None,
+            })
+        }
+    }
+
+    impl From<ParamsUpdate> for RawParams {
+        fn from(value: ParamsUpdate) -> Self {
+            RawParams {
+                block: value.block.map(From::from),
+                evidence: value.evidence.map(From::from),
+                validator: value.validator.map(From::from),
+                version: value.version.map(From::from),
+            }
+        }
+    }
+
     impl Protobuf<RawValidatorParams> for ValidatorParams {}
 
     impl TryFrom<RawValidatorParams> for ValidatorParams {
@@ -261,7 +516,11 @@ mod v0_37 {
 
         fn try_from(value: RawValidatorParams) -> Result<Self, Self::Error> {
             Ok(Self {
-                pub_key_types: value.pub_key_types.iter().map(|f| key_type(f)).collect(),
+                pub_key_types: value
+                    .pub_key_types
+                    .iter()
+                    .map(|s| public_key::Algorithm::try_from(s.as_str()))
+                    .collect::<Result<_, _>>()?,
             })
         }
     }
@@ -275,6 +534,8 @@ mod v0_37 {
                     .map(|k| match k {
                         public_key::Algorithm::Ed25519 => "ed25519".to_string(),
                         public_key::Algorithm::Secp256k1 => "secp256k1".to_string(),
+                        public_key::Algorithm::Bls12_381 => "bls12_381".to_string(),
+                        public_key::Algorithm::Sr25519 => "sr25519".to_string(),
                     })
                     .collect(),
             }
@@ -305,7 +566,7 @@ mod v0_38 {
     };
     use tendermint_proto::Protobuf;
 
-    use super::{key_type, AbciParams, Params, ValidatorParams, VersionParams};
+    use super::{AbciParams, Params, ParamsUpdate, ValidatorParams, VersionParams};
     use crate::{error::Error, prelude::*, public_key};
 
     impl Protobuf<RawParams> for Params {}
@@ -349,6 +610,32 @@ mod v0_38 {
         }
     }
 
+    impl TryFrom<RawParams> for ParamsUpdate {
+        type Error = Error;
+
+        fn try_from(value: RawParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                block: value.block.map(TryFrom::try_from).transpose()?,
+                evidence: value.evidence.map(TryFrom::try_from).transpose()?,
+                validator: value.validator.map(TryFrom::try_from).transpose()?,
+                version: value.version.map(TryFrom::try_from).transpose()?,
+                abci: value.abci.map(TryFrom::try_from).transpose()?,
+            })
+        }
+    }
+
+    impl From<ParamsUpdate> for RawParams {
+        fn from(value: ParamsUpdate) -> Self {
+            RawParams {
+                block: value.block.map(From::from),
+                evidence: value.evidence.map(From::from),
+                validator: value.validator.map(From::from),
+                version: value.version.map(From::from),
+                abci: value.abci.map(From::from),
+            }
+        }
+    }
+
     impl Protobuf<RawValidatorParams> for ValidatorParams {}
 
     impl TryFrom<RawValidatorParams> for ValidatorParams {
@@ -356,7 +643,11 @@ mod v0_38 {
 
         fn try_from(value: RawValidatorParams) -> Result<Self, Self::Error> {
             Ok(Self {
-                pub_key_types: value.pub_key_types.iter().map(|f| key_type(f)).collect(),
+                pub_key_types: value
+                    .pub_key_types
+                    .iter()
+                    .map(|s| public_key::Algorithm::try_from(s.as_str()))
+                    .collect::<Result<_, _>>()?,
             })
         }
     }
@@ -370,6 +661,8 @@ mod v0_38 {
                     .map(|k| match k {
                         public_key::Algorithm::Ed25519 => "ed25519".to_string(),
                         public_key::Algorithm::Secp256k1 => "secp256k1".to_string(),
+                        public_key::Algorithm::Bls12_381 => "bls12_381".to_string(),
+                        public_key::Algorithm::Sr25519 => "sr25519".to_string(),
                     })
                     .collect(),
             }
@@ -418,3 +711,145 @@ mod v0_38 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> Params {
+        serde_json::from_str(
+            r#"{
+                "block": { "max_bytes": "22020096", "max_gas": "-1" },
+                "evidence": {
+                    "max_age_num_blocks": "100000",
+                    "max_age_duration": "172800000000000",
+                    "max_bytes": "1048576"
+                },
+                "validator": { "pub_key_types": ["ed25519"] },
+                "version": { "app": "0" },
+                "abci": { "vote_extensions_enable_height": "0" }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn validates_params() {
+        params().validate_basic().unwrap();
+
+        let mut invalid = params();
+        invalid.block.max_bytes = block::Size::MAX_BYTES + 1;
+        assert!(invalid.validate_basic().is_err());
+
+        let mut invalid = params();
+        invalid.evidence.max_bytes = invalid.block.max_bytes as i64 + 1;
+        assert!(invalid.validate_basic().is_err());
+
+        let mut invalid = params();
+        invalid.validator.pub_key_types.clear();
+        assert!(invalid.validate_basic().is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_pub_key_types() {
+        use tendermint_proto::v0_38::types::ValidatorParams as RawValidatorParams;
+
+        let raw = |types: &[&str]| RawValidatorParams {
+            pub_key_types: types.iter().map(|t| t.to_string()).collect(),
+        };
+        let params = ValidatorParams::try_from(raw(&["ed25519", "Secp256k1"])).unwrap();
+        assert_eq!(
+            params.pub_key_types,
+            [
+                public_key::Algorithm::Ed25519,
+                public_key::Algorithm::Secp256k1
+            ]
+        );
+        assert!(ValidatorParams::try_from(raw(&["ed25519", "rsa"])).is_err());
+    }
+
+    #[test]
+    fn applies_partial_update() {
+        let params = params();
+        let update = ParamsUpdate {
+            block: Some(block::Size {
+                max_bytes: 1024,
+                max_gas: 100,
+                time_iota_ms: block::Size::default_time_iota_ms(),
+            }),
+            ..Default::default()
+        };
+        let updated = params.update(&update);
+        assert_eq!(updated.block.max_bytes, 1024);
+        assert_eq!(updated.block.max_gas, 100);
+        assert_eq!(updated.evidence, params.evidence);
+        assert_eq!(updated.validator, params.validator);
+
+        assert_eq!(params.update(&ParamsUpdate::default()), params);
+        assert_eq!(params.update(&params.clone().into()), params);
+    }
+
+    #[test]
+    fn validates_vote_extensions_update() {
+        let enable_at = |height: u32| ParamsUpdate {
+            abci: Some(AbciParams {
+                vote_extensions_enable_height: Some(height.into()),
+            }),
+            ..Default::default()
+        };
+
+        let params = params();
+        params
+            .validate_update(&enable_at(11), 10u32.into())
+            .unwrap();
+        assert!(params
+            .validate_update(&enable_at(10), 10u32.into())
+            .is_err());
+
+        let params = params.update(&enable_at(11));
+        params
+            .validate_update(&enable_at(12), 10u32.into())
+            .unwrap();
+        assert!(params
+            .validate_update(&enable_at(12), 11u32.into())
+            .is_err());
+        assert!(params
+            .validate_update(&ParamsUpdate::from(params.clone()), 11u32.into())
+            .is_ok());
+    }
+
+    #[test]
+    fn json_omits_unset_abci_params() {
+        let mut params = params();
+        params.abci = AbciParams::default();
+        let json = serde_json::to_value(&params).unwrap();
+        assert!(json.get("abci").is_none());
+        let parsed: Params = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.abci, AbciParams::default());
+
+        params.abci.vote_extensions_enable_height = Some(10u32.into());
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(json["abci"]["vote_extensions_enable_height"], "10");
+    }
+
+    #[test]
+    fn json_encodes_unset_version_as_empty_object() {
+        let mut params = params();
+        params.version = None;
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(json["version"], serde_json::json!({}));
+        let parsed: Params = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.version, None);
+    }
+
+    #[cfg(feature = "rust-crypto")]
+    #[test]
+    fn hash_matches_header() {
+        let expected = Hash::from_hex_upper(
+            crate::hash::Algorithm::Sha256,
+            "048091BC7DDC283F77BFBF91D73C44DA58C3DF8A9CBC867405D8B7F3DAADA22F",
+        )
+        .unwrap();
+        assert_eq!(params().hash(), expected);
+    }
+}
@@ -11,6 +11,11 @@ pub mod ed25519;
 pub mod sha256;
 pub mod signature;
 
+#[cfg(feature = "bls12-381")]
+pub mod bls12_381;
+#[cfg(feature = "sr25519")]
+pub mod sr25519;
+
 pub use sha256::Sha256;
 
 #[cfg(feature = "rust-crypto")]
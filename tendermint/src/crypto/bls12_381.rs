@@ -0,0 +1,55 @@
+//! BLS12-381 keys, as used by CometBFT validators.
+//!
+//! Keys use the "minimal public key size" variant: public keys are
+//! compressed G1 points and signatures are compressed G2 points.
+
+use crate::Error;
+
+/// Length of a compressed BLS12-381 public key, in bytes.
+pub const PUBLIC_KEY_LENGTH: usize = 48;
+
+/// Length of a compressed BLS12-381 signature, in bytes.
+pub const SIGNATURE_LENGTH: usize = 96;
+
+/// Messages longer than this are hashed with SHA-256 before being signed.
+pub const MAX_MESSAGE_LENGTH: usize = 32;
+
+/// Domain separation tag used when hashing messages to the curve.
+pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct VerificationKey([u8; PUBLIC_KEY_LENGTH]);
+
+impl core::fmt::Display for VerificationKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for VerificationKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <Self as core::fmt::Display>::fmt(self, f)
+    }
+}
+
+impl VerificationKey {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<&'_ [u8]> for VerificationKey {
+    type Error = Error;
+
+    fn try_from(slice: &'_ [u8]) -> Result<Self, Self::Error> {
+        if slice.len() != PUBLIC_KEY_LENGTH {
+            return Err(Error::invalid_key("invalid bls12_381 key length".into()));
+        }
+        let mut bytes = [0u8; PUBLIC_KEY_LENGTH];
+        bytes[..].copy_from_slice(slice);
+        Ok(Self(bytes))
+    }
+}
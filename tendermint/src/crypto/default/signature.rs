@@ -1,7 +1,7 @@
-//! The pure Rust implementation of signature verification functions.
+//! The pure Rust implementation of signature generation and verification functions.
 
-use crate::crypto::signature::Error;
-use crate::{PublicKey, Signature};
+use crate::crypto::signature::{Error, Signer};
+use crate::{private_key, PrivateKey, PublicKey, Signature};
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct Verifier;
@@ -27,16 +27,125 @@ impl crate::crypto::signature::Verifier for Verifier {
                     .map_err(|_| Error::MalformedSignature)?;
                 pk.verify(msg, &sig).map_err(|_| Error::VerificationFailed)
             },
+            #[cfg(feature = "bls12-381")]
+            PublicKey::Bls12_381(pk) => {
+                use crate::crypto::bls12_381::{DST, MAX_MESSAGE_LENGTH};
+
+                let pubkey = blst::min_pk::PublicKey::key_validate(pk.as_bytes())
+                    .map_err(|_| Error::MalformedPublicKey)?;
+                let sig = blst::min_pk::Signature::sig_validate(signature.as_bytes(), true)
+                    .map_err(|_| Error::MalformedSignature)?;
+                // Long messages are signed by their SHA-256 digest.
+                let digest;
+                let msg = if msg.len() > MAX_MESSAGE_LENGTH {
+                    digest = <super::Sha256 as crate::crypto::Sha256>::digest(msg);
+                    &digest[..]
+                } else {
+                    msg
+                };
+                match sig.verify(false, msg, DST, &[], &pubkey, false) {
+                    blst::BLST_ERROR::BLST_SUCCESS => Ok(()),
+                    _ => Err(Error::VerificationFailed),
+                }
+            },
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(pk) => {
+                use crate::crypto::sr25519::SIGNING_CONTEXT;
+
+                let pubkey = schnorrkel::PublicKey::from_bytes(pk.as_bytes())
+                    .map_err(|_| Error::MalformedPublicKey)?;
+                let sig = schnorrkel::Signature::from_bytes(signature.as_bytes())
+                    .map_err(|_| Error::MalformedSignature)?;
+                pubkey
+                    .verify_simple(SIGNING_CONTEXT, msg, &sig)
+                    .map_err(|_| Error::VerificationFailed)
+            },
             _ => Err(Error::UnsupportedKeyType),
         }
     }
+
+    /// Ed25519 signatures in the batch are checked together with a single
+    /// multiscalar multiplication; signatures of other key types are
+    /// verified individually.
+    #[cfg(feature = "std")]
+    fn verify_batch(&self, batch: &[(PublicKey, &[u8], &Signature)]) -> Result<(), Error> {
+        let mut ed25519_batch = ed25519_consensus::batch::Verifier::new();
+        for (pubkey, msg, signature) in batch {
+            #[allow(unreachable_patterns)]
+            match pubkey {
+                PublicKey::Ed25519(pk) => {
+                    let pubkey = ed25519_consensus::VerificationKeyBytes::try_from(pk.as_bytes())
+                        .map_err(|_| Error::MalformedPublicKey)?;
+                    let sig = ed25519_consensus::Signature::try_from(signature.as_bytes())
+                        .map_err(|_| Error::MalformedSignature)?;
+                    ed25519_batch.queue((pubkey, sig, *msg));
+                },
+                _ => self.verify(*pubkey, msg, signature)?,
+            }
+        }
+        ed25519_batch
+            .verify(rand_core::OsRng)
+            .map_err(|_| Error::VerificationFailed)
+    }
+}
+
+impl Signer for ed25519_consensus::SigningKey {
+    fn public_key(&self) -> PublicKey {
+        PublicKey::Ed25519(self.verification_key().into())
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<Signature, Error> {
+        Ok(ed25519_consensus::SigningKey::sign(self, msg).into())
+    }
+}
+
+impl Signer for private_key::Ed25519 {
+    fn public_key(&self) -> PublicKey {
+        PublicKey::Ed25519(self.verification_key())
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<Signature, Error> {
+        let signing_key = ed25519_consensus::SigningKey::try_from(self.as_bytes())
+            .map_err(|_| Error::SigningFailed)?;
+        Ok(signing_key.sign(msg).into())
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl Signer for private_key::Secp256k1 {
+    fn public_key(&self) -> PublicKey {
+        PublicKey::Secp256k1(*self.verifying_key())
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<Signature, Error> {
+        use signature::Signer as _;
+
+        let sig: k256::ecdsa::Signature = self.try_sign(msg).map_err(|_| Error::SigningFailed)?;
+        Ok(sig.into())
+    }
+}
+
+impl Signer for PrivateKey {
+    fn public_key(&self) -> PublicKey {
+        PrivateKey::public_key(self)
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<Signature, Error> {
+        match self {
+            PrivateKey::Ed25519(signing_key) => signing_key.sign(msg),
+
+            #[cfg(feature = "secp256k1")]
+            PrivateKey::Secp256k1(signing_key) => Signer::sign(signing_key, msg),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::crypto::default::signature::Verifier;
-    use crate::crypto::signature::Verifier as _;
-    use crate::{PublicKey, Signature};
+    use crate::crypto::signature::{Signer, Verifier as _};
+    use crate::prelude::*;
+    use crate::{private_key, PrivateKey, PublicKey, Signature};
 
     // From https://datatracker.ietf.org/doc/html/rfc8032#section-7.1
     // Each test vector consists of: [public_key, message, signature].
@@ -192,7 +301,7 @@ mod tests {
             let public_key = PublicKey::from_raw_ed25519(public_key).unwrap();
             match public_key {
                 PublicKey::Ed25519(_) => {},
-                #[cfg(feature = "secp256k1")]
+                #[cfg(any(feature = "secp256k1", feature = "bls12-381", feature = "sr25519"))]
                 _ => panic!("expected public key to be Ed25519: {:?}", public_key),
             }
             let sig = Signature::try_from(sig).unwrap();
@@ -203,6 +312,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ed25519_verify_batch() {
+        let mut batch = ED25519_TEST_VECTORS
+            .iter()
+            .map(|v| {
+                let public_key = PublicKey::from_raw_ed25519(v[0]).unwrap();
+                let sig = Signature::try_from(v[2]).unwrap();
+                (public_key, v[1], sig)
+            })
+            .collect::<Vec<_>>();
+
+        let items = batch
+            .iter()
+            .map(|(pk, msg, sig)| (*pk, *msg, sig))
+            .collect::<Vec<_>>();
+        Verifier.verify_batch(&items).unwrap();
+
+        // Swap the messages of two entries to invalidate their signatures.
+        let msg = batch[0].1;
+        batch[0].1 = batch[1].1;
+        batch[1].1 = msg;
+        let items = batch
+            .iter()
+            .map(|(pk, msg, sig)| (*pk, *msg, sig))
+            .collect::<Vec<_>>();
+        assert!(Verifier.verify_batch(&items).is_err());
+    }
+
     // Arbitrary "valid" tests taken from
     // https://github.com/google/wycheproof/blob/2196000605e45d91097147c9c71f26b72af58003/testvectors/ecdsa_secp256k1_sha256_test.json
     //
@@ -293,4 +430,80 @@ mod tests {
                 .unwrap_or_else(|_| panic!("signature should be valid for test vector {}", i));
         }
     }
+
+    #[test]
+    fn ed25519_sign_and_verify() {
+        let signing_key = private_key::Ed25519::try_from(&[7u8; 32][..]).unwrap();
+        let private_key = PrivateKey::Ed25519(signing_key.clone());
+        let msg = b"hello world";
+
+        let sig = Signer::sign(&private_key, msg).unwrap();
+        assert_eq!(sig, Signer::sign(&signing_key, msg).unwrap());
+
+        let verifier = Verifier;
+        verifier
+            .verify(Signer::public_key(&private_key), msg, &sig)
+            .unwrap();
+        assert!(verifier
+            .verify(Signer::public_key(&private_key), b"goodbye", &sig)
+            .is_err());
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn secp256k1_sign_and_verify() {
+        let signing_key = private_key::Secp256k1::from_slice(&[7u8; 32]).unwrap();
+        let private_key = PrivateKey::Secp256k1(signing_key);
+        let msg = b"hello world";
+
+        let sig = Signer::sign(&private_key, msg).unwrap();
+
+        let verifier = Verifier;
+        verifier
+            .verify(Signer::public_key(&private_key), msg, &sig)
+            .unwrap();
+        assert!(verifier
+            .verify(Signer::public_key(&private_key), b"goodbye", &sig)
+            .is_err());
+    }
+
+    #[cfg(feature = "bls12-381")]
+    #[test]
+    fn bls12_381_verify() {
+        use crate::crypto::bls12_381::DST;
+
+        let secret_key = blst::min_pk::SecretKey::key_gen(&[7u8; 32], &[]).unwrap();
+        let public_key = PublicKey::from_raw_bls12_381(&secret_key.sk_to_pk().compress()).unwrap();
+        let verifier = Verifier;
+
+        // Short messages are signed as-is.
+        let msg = b"hello world";
+        let sig = Signature::try_from(&secret_key.sign(msg, DST, &[]).compress()[..]).unwrap();
+        verifier.verify(public_key, msg, &sig).unwrap();
+        assert!(verifier.verify(public_key, b"goodbye", &sig).is_err());
+
+        // Long messages are signed by their SHA-256 digest.
+        let msg = [42u8; 100];
+        let digest = <crate::crypto::default::Sha256 as crate::crypto::Sha256>::digest(msg);
+        let sig = Signature::try_from(&secret_key.sign(&digest, DST, &[]).compress()[..]).unwrap();
+        verifier.verify(public_key, &msg, &sig).unwrap();
+    }
+
+    #[cfg(feature = "sr25519")]
+    #[test]
+    fn sr25519_verify() {
+        use crate::crypto::sr25519::SIGNING_CONTEXT;
+
+        let keypair = schnorrkel::MiniSecretKey::from_bytes(&[7u8; 32])
+            .unwrap()
+            .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519);
+        let public_key = PublicKey::from_raw_sr25519(&keypair.public.to_bytes()).unwrap();
+        let msg = b"hello world";
+        let sig = keypair.sign_simple(SIGNING_CONTEXT, msg);
+        let sig = Signature::try_from(&sig.to_bytes()[..]).unwrap();
+
+        let verifier = Verifier;
+        verifier.verify(public_key, msg, &sig).unwrap();
+        assert!(verifier.verify(public_key, b"goodbye", &sig).is_err());
+    }
 }
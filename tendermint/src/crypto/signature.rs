@@ -14,6 +14,10 @@ pub enum Error {
     MalformedPublicKey,
     /// The signature data was malformed.
     MalformedSignature,
+    /// The signer failed to produce a signature.
+    ///
+    /// This variant is deliberately opaque as to avoid side-channel leakage.
+    SigningFailed,
 }
 
 impl Display for Error {
@@ -23,6 +27,7 @@ impl Display for Error {
             Error::UnsupportedKeyType => f.write_str("key type not supported"),
             Error::MalformedPublicKey => f.write_str("malformed public key encoding"),
             Error::MalformedSignature => f.write_str("malformed signature"),
+            Error::SigningFailed => f.write_str("signing failed"),
         }
     }
 }
@@ -32,4 +37,29 @@ impl std::error::Error for Error {}
 
 pub trait Verifier {
     fn verify(&self, pubkey: PublicKey, msg: &[u8], signature: &Signature) -> Result<(), Error>;
+
+    /// Verify a batch of signatures, each given as a `(pubkey, msg, signature)` triple.
+    ///
+    /// Succeeds only if every signature in the batch is valid. A failure does not
+    /// tell which signature is invalid; callers that need to know should fall back
+    /// to checking each one with [`Verifier::verify`].
+    ///
+    /// The default implementation verifies the signatures one by one.
+    fn verify_batch(&self, batch: &[(PublicKey, &[u8], &Signature)]) -> Result<(), Error> {
+        batch
+            .iter()
+            .try_for_each(|(pubkey, msg, signature)| self.verify(*pubkey, msg, signature))
+    }
+}
+
+/// Produces signatures over messages with a private key.
+///
+/// The key material does not need to be held in memory: implementations
+/// may delegate to an HSM, a KMS or a remote signer.
+pub trait Signer {
+    /// Get the public key corresponding to the signing key.
+    fn public_key(&self) -> PublicKey;
+
+    /// Sign the given message.
+    fn sign(&self, msg: &[u8]) -> Result<Signature, Error>;
 }
@@ -0,0 +1,46 @@
+//! Sr25519 (Schnorr over Ristretto25519) keys.
+
+use crate::Error;
+
+/// Length of an Sr25519 public key, in bytes.
+pub const PUBLIC_KEY_LENGTH: usize = 32;
+
+/// Signing context used by Tendermint for Sr25519 signatures.
+pub const SIGNING_CONTEXT: &[u8] = b"";
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct VerificationKey([u8; PUBLIC_KEY_LENGTH]);
+
+impl core::fmt::Display for VerificationKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for VerificationKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <Self as core::fmt::Display>::fmt(self, f)
+    }
+}
+
+impl VerificationKey {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<&'_ [u8]> for VerificationKey {
+    type Error = Error;
+
+    fn try_from(slice: &'_ [u8]) -> Result<Self, Self::Error> {
+        if slice.len() != PUBLIC_KEY_LENGTH {
+            return Err(Error::invalid_key("invalid sr25519 key length".into()));
+        }
+        let mut bytes = [0u8; PUBLIC_KEY_LENGTH];
+        bytes[..].copy_from_slice(slice);
+        Ok(Self(bytes))
+    }
+}
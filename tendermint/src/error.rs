@@ -1,12 +1,12 @@
 //! Error types
 
-use alloc::string::String;
+use alloc::{boxed::Box, string::String};
 use core::num::TryFromIntError;
 
 use flex_error::{define_error, DisplayOnly};
 use serde::{Deserialize, Serialize};
 
-use crate::account;
+use crate::{account, vote::Vote};
 
 define_error! {
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -100,6 +100,14 @@ define_error! {
             { detail : String }
             |e| { format_args!("invalid part set header: {}", e.detail) },
 
+        InvalidPart
+            { reason: String }
+            |e| { format_args!("invalid block part: {}", e.reason) },
+
+        IncompletePartSet
+            { count: u32, total: u32 }
+            |e| { format_args!("incomplete part set: {} of {} parts", e.count, e.total) },
+
         MissingHeader
             |_| { format_args!("missing header field") },
 
@@ -157,12 +165,24 @@ define_error! {
         InvalidEvidence
             |_| { format_args!("invalid evidence") },
 
+        EvidenceVerification
+            { reason: String }
+            |e| { format_args!("invalid evidence: {}", e.reason) },
+
         InvalidValidatorParams
             |_| { format_args!("invalid validator parameters") },
 
         InvalidVersionParams
             |_| { format_args!("invalid version parameters") },
 
+        InvalidConsensusParams
+            { reason: String }
+            |e| { format_args!("invalid consensus params: {}", e.reason) },
+
+        InvalidGenesis
+            { reason: String }
+            |e| { format_args!("invalid genesis: {}", e.reason) },
+
         InvalidAbciRequestType
             |_| { format_args!("invalid ABCI request type") },
 
@@ -213,6 +233,10 @@ define_error! {
         Signature
             |_| { "signature error" },
 
+        Signing
+            [ DisplayOnly<crate::crypto::signature::Error> ]
+            |_| { "failed to sign message" },
+
         TrustThresholdTooLarge
             |_| { "trust threshold is too large (must be <= 1)" },
 
@@ -230,6 +254,49 @@ define_error! {
             [ DisplayOnly<TryFromIntError> ]
             |_| { "negative item index in proof" },
 
+        InvalidMerkleProof
+            { reason: String }
+            | e | { format_args!("invalid Merkle proof: {}", e.reason) },
+
+        UnrecognizedProofOp
+            { field_type: String }
+            | e | { format_args!("unrecognized proof op type: {}", e.field_type) },
+
+        InvalidCommit
+            { reason: String }
+            |e| { format_args!("{}", e.reason) },
+
+        InvalidCommitSignature
+            { index: usize, validator: account::Id }
+            |e| { format_args!("wrong signature (#{}) from validator {}", e.index, e.validator) },
+
+        DuplicateValidatorVote
+            { validator: account::Id, first_index: usize, second_index: usize }
+            |e| { format_args!("double vote from {} ({} and {})", e.validator, e.first_index, e.second_index) },
+
+        NotEnoughVotingPowerSigned
+            { got: u64, needed: u64 }
+            |e| { format_args!("invalid commit -- insufficient voting power: got {}, needed more than {}", e.got, e.needed) },
+
+        InvalidVote
+            { reason: String }
+            |e| { format_args!("invalid vote: {}", e.reason) },
+
+        InvalidProposal
+            { reason: String }
+            |e| { format_args!("invalid proposal: {}", e.reason) },
+
+        ConflictingVotes
+            { vote_a: Box<Vote>, vote_b: Box<Vote> }
+            |e| {
+                format_args!("conflicting votes from validator {}",
+                    e.vote_a.validator_address)
+            },
+
+        InvalidValidatorUpdate
+            { reason: String }
+            |e| { format_args!("invalid validator update: {}", e.reason) },
+
         TotalVotingPowerMismatch
             |_| { "total voting power in validator set does not match the sum of participants' powers" },
 
@@ -4,15 +4,24 @@ use core::slice;
 
 use serde::{Deserialize, Serialize};
 use tendermint_proto::google::protobuf::Duration as RawDuration;
+use tendermint_proto::v0_37::types::{
+    DuplicateVoteEvidence as RawDuplicateVoteEvidence,
+    LightClientAttackEvidence as RawLightClientAttackEvidence, PartSetHeader as RawPartSetHeader,
+};
 use tendermint_proto::Protobuf;
 
 use crate::{
-    block::{signed_header::SignedHeader, Height},
+    block::{self, signed_header::SignedHeader, Height},
+    chain,
+    crypto::{signature::Verifier, Sha256},
     error::Error,
+    merkle::{self, MerkleHash},
     prelude::*,
-    serializers, validator,
+    serializers,
+    trust_threshold::TrustThresholdFraction,
+    validator,
     vote::Power,
-    Time, Vote,
+    Hash, Time, Vote,
 };
 
 /// Evidence of malfeasance by validators (i.e. signing conflicting votes or light client attack).
@@ -25,6 +34,88 @@ pub enum Evidence {
     LightClientAttack(Box<LightClientAttackEvidence>),
 }
 
+impl Evidence {
+    /// Protobuf encoding of the evidence, as hashed into the evidence hash
+    /// of a block.
+    fn hash_bytes(&self) -> Vec<u8> {
+        match self {
+            Evidence::DuplicateVote(ev) => {
+                Protobuf::<RawDuplicateVoteEvidence>::encode_vec(ev.as_ref().clone())
+            },
+            Evidence::LightClientAttack(ev) => {
+                Protobuf::<RawLightClientAttackEvidence>::encode_vec(ev.as_ref().clone())
+            },
+        }
+    }
+
+    /// The height at which the misbehavior occurred: the height of the
+    /// votes, or the common height of a light client attack.
+    pub fn height(&self) -> Height {
+        match self {
+            Evidence::DuplicateVote(ev) => ev.vote_a.height,
+            Evidence::LightClientAttack(ev) => ev.common_height,
+        }
+    }
+
+    /// The time of the block at the height of the evidence.
+    pub fn time(&self) -> Time {
+        match self {
+            Evidence::DuplicateVote(ev) => ev.timestamp,
+            Evidence::LightClientAttack(ev) => ev.timestamp,
+        }
+    }
+
+    /// Performs basic validation of the evidence, without verifying it
+    /// against the state of the chain.
+    #[cfg(feature = "rust-crypto")]
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        self.validate_basic_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Validate this evidence with a Merkle hasher provided by a crypto
+    /// provider.
+    pub fn validate_basic_with<H>(&self) -> Result<(), Error>
+    where
+        H: MerkleHash + Sha256 + Default,
+    {
+        match self {
+            Evidence::DuplicateVote(ev) => ev.validate_basic(),
+            Evidence::LightClientAttack(ev) => ev.validate_basic_with::<H>(),
+        }
+    }
+
+    /// Check that the evidence has not expired, given the height and time of
+    /// the last block.
+    ///
+    /// Evidence expires once it is older than both the maximum age in blocks
+    /// and the maximum age in time.
+    pub fn verify_age(
+        &self,
+        params: &Params,
+        last_block_height: Height,
+        last_block_time: Time,
+    ) -> Result<(), Error> {
+        let age_num_blocks = last_block_height
+            .value()
+            .saturating_sub(self.height().value());
+        let age_duration = last_block_time
+            .duration_since(self.time())
+            .unwrap_or_default();
+        if age_duration > params.max_age_duration.0 && age_num_blocks > params.max_age_num_blocks {
+            return Err(Error::evidence_verification(format!(
+                "evidence from height {} (created at: {}) is too old; min height is {} and evidence can not be older than {:?}",
+                self.height(),
+                self.time(),
+                last_block_height
+                    .value()
+                    .saturating_sub(params.max_age_num_blocks),
+                params.max_age_duration.0
+            )));
+        }
+        Ok(())
+    }
+}
+
 impl From<LightClientAttackEvidence> for Evidence {
     fn from(ev: LightClientAttackEvidence) -> Self {
         Self::LightClientAttack(Box::new(ev))
@@ -64,10 +155,151 @@ impl DuplicateVoteEvidence {
         })
     }
 
+    /// Create the evidence of two conflicting votes from a validator of
+    /// the given set, as produced by CometBFT.
+    ///
+    /// The votes are ordered by block ID, and `block_time` is the time of the
+    /// block at the height of the votes.
+    pub fn from_conflicting_votes(
+        vote1: Vote,
+        vote2: Vote,
+        block_time: Time,
+        validator_set: &validator::Set,
+    ) -> Result<Self, Error> {
+        let validator = validator_set
+            .validator(vote1.validator_address)
+            .ok_or_else(Error::missing_validator)?;
+        let (vote_a, vote_b) = if block_key(&vote1) < block_key(&vote2) {
+            (vote1, vote2)
+        } else {
+            (vote2, vote1)
+        };
+        Ok(Self {
+            vote_a,
+            vote_b,
+            total_voting_power: validator_set.total_voting_power(),
+            validator_power: validator.power,
+            timestamp: block_time,
+        })
+    }
+
     /// Get votes
     pub fn votes(&self) -> (&Vote, &Vote) {
         (&self.vote_a, &self.vote_b)
     }
+
+    /// Performs basic validation of the evidence, without verifying the
+    /// signatures.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        for vote in [&self.vote_a, &self.vote_b] {
+            if vote.signature.is_none() {
+                return Err(Error::evidence_verification(format!(
+                    "missing signature in vote from {}",
+                    vote.validator_address
+                )));
+            }
+        }
+        if block_key(&self.vote_a) >= block_key(&self.vote_b) {
+            return Err(Error::evidence_verification(
+                "duplicate votes in invalid order".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Verify the evidence against the validator set at the height of the
+    /// votes.
+    ///
+    /// Checks that both votes are from the same validator for the same
+    /// height, round and type, but for different blocks, that they are
+    /// correctly signed, and that the voting powers match the validator set.
+    pub fn verify<V>(
+        &self,
+        verifier: &V,
+        chain_id: &chain::Id,
+        validator_set: &validator::Set,
+    ) -> Result<(), Error>
+    where
+        V: Verifier,
+    {
+        let (vote_a, vote_b) = (&self.vote_a, &self.vote_b);
+        let validator = validator_set
+            .validator(vote_a.validator_address)
+            .ok_or_else(|| {
+                Error::evidence_verification(format!(
+                    "address {} was not a validator at height {}",
+                    vote_a.validator_address, vote_a.height
+                ))
+            })?;
+
+        if (vote_a.height, vote_a.round, vote_a.vote_type)
+            != (vote_b.height, vote_b.round, vote_b.vote_type)
+        {
+            return Err(Error::evidence_verification(format!(
+                "h/r/s does not match: {}/{}/{} vs {}/{}/{}",
+                vote_a.height,
+                vote_a.round,
+                vote_a.vote_type,
+                vote_b.height,
+                vote_b.round,
+                vote_b.vote_type
+            )));
+        }
+        if vote_a.validator_address != vote_b.validator_address {
+            return Err(Error::evidence_verification(format!(
+                "validator addresses do not match: {} vs {}",
+                vote_a.validator_address, vote_b.validator_address
+            )));
+        }
+        if vote_a.block_id == vote_b.block_id {
+            return Err(Error::evidence_verification(
+                "block IDs are the same - not a real duplicate vote".to_string(),
+            ));
+        }
+        if validator.power != self.validator_power {
+            return Err(Error::evidence_verification(format!(
+                "validator power from evidence and our validator set does not match ({} != {})",
+                self.validator_power, validator.power
+            )));
+        }
+        check_total_voting_power(self.total_voting_power, validator_set)?;
+
+        for (name, vote) in [("VoteA", vote_a), ("VoteB", vote_b)] {
+            let signature = vote.signature.as_ref().ok_or_else(|| {
+                Error::evidence_verification(format!("missing signature in {name}"))
+            })?;
+            let sign_bytes = vote.clone().into_signable_vec(chain_id.clone());
+            validator
+                .verify_signature(verifier, &sign_bytes, signature)
+                .map_err(|e| {
+                    Error::evidence_verification(format!("verifying {name}: {}", e.detail()))
+                })?;
+        }
+        Ok(())
+    }
+}
+
+/// Check that the total voting power of the evidence matches the validator
+/// set.
+fn check_total_voting_power(power: Power, validator_set: &validator::Set) -> Result<(), Error> {
+    if power != validator_set.total_voting_power() {
+        return Err(Error::evidence_verification(format!(
+            "total voting power from the evidence and our validator set does not match ({} != {})",
+            power,
+            validator_set.total_voting_power()
+        )));
+    }
+    Ok(())
+}
+
+/// The key by which CometBFT orders the block IDs of votes.
+fn block_key(vote: &Vote) -> Vec<u8> {
+    let block_id = vote.block_id.unwrap_or_default();
+    let mut key = block_id.hash.as_bytes().to_vec();
+    key.extend(Protobuf::<RawPartSetHeader>::encode_vec(
+        block_id.part_set_header,
+    ));
+    key
 }
 
 /// Conflicting block detected in light client attack
@@ -87,6 +319,252 @@ pub struct LightClientAttackEvidence {
     pub timestamp: Time,
 }
 
+impl LightClientAttackEvidence {
+    /// Performs basic validation of the evidence, including the consistency
+    /// of the conflicting block, without verifying it against the state of
+    /// the chain.
+    #[cfg(feature = "rust-crypto")]
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        self.validate_basic_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Validate this evidence with a Merkle hasher provided by a crypto
+    /// provider.
+    pub fn validate_basic_with<H>(&self) -> Result<(), Error>
+    where
+        H: MerkleHash + Sha256 + Default,
+    {
+        let signed_header = &self.conflicting_block.signed_header;
+        let (header, commit) = (&signed_header.header, &signed_header.commit);
+
+        if self.total_voting_power.value() == 0 {
+            return Err(Error::evidence_verification(
+                "negative or zero total voting power".to_string(),
+            ));
+        }
+        if self.common_height.value() == 0 {
+            return Err(Error::evidence_verification(
+                "negative or zero common height".to_string(),
+            ));
+        }
+        // The common height may be the height of the conflicting block in
+        // the case of an equivocation or amnesia attack.
+        if self.common_height > header.height {
+            return Err(Error::evidence_verification(format!(
+                "common height is ahead of the conflicting block height ({} > {})",
+                self.common_height, header.height
+            )));
+        }
+
+        let invalid_block = |reason: String| {
+            Error::evidence_verification(format!("invalid conflicting light block: {reason}"))
+        };
+        if header.height != commit.height {
+            return Err(invalid_block(format!(
+                "header and commit height mismatch: {} vs {}",
+                header.height, commit.height
+            )));
+        }
+        commit
+            .validate_basic()
+            .map_err(|e| invalid_block(e.detail().to_string()))?;
+        let header_hash = header.hash_with::<H>();
+        if commit.block_id.hash != header_hash {
+            return Err(invalid_block(format!(
+                "commit signs block {}, header is block {}",
+                commit.block_id.hash, header_hash
+            )));
+        }
+        let validators_hash = self.conflicting_block.validator_set.hash_with::<H>();
+        if header.validators_hash != validators_hash {
+            return Err(invalid_block(format!(
+                "expected validator hash of header to match validator set hash ({} != {})",
+                header.validators_hash, validators_hash
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether the conflicting header could not have been derived from the
+    /// state of the chain, i.e. whether this is a lunatic attack.
+    pub fn conflicting_header_is_invalid(&self, trusted_header: &block::Header) -> bool {
+        let conflicting = &self.conflicting_block.signed_header.header;
+        trusted_header.validators_hash != conflicting.validators_hash
+            || trusted_header.next_validators_hash != conflicting.next_validators_hash
+            || trusted_header.consensus_hash != conflicting.consensus_hash
+            || trusted_header.app_hash != conflicting.app_hash
+            || trusted_header.last_results_hash != conflicting.last_results_hash
+    }
+
+    /// Find the validators who took part in the attack, ordered by voting
+    /// power.
+    ///
+    /// In a lunatic attack, these are the validators of `common_vals` who
+    /// signed the conflicting block. In an equivocation attack, they are the
+    /// validators who signed both the trusted and the conflicting blocks. In
+    /// an amnesia attack, where the blocks were committed in different
+    /// rounds, the malicious validators cannot be determined.
+    pub fn byzantine_validators(
+        &self,
+        common_vals: &validator::Set,
+        trusted: &SignedHeader,
+    ) -> Vec<validator::Info> {
+        let conflicting_commit = &self.conflicting_block.signed_header.commit;
+        let mut validators = if self.conflicting_header_is_invalid(&trusted.header) {
+            conflicting_commit
+                .signatures
+                .iter()
+                .filter(|sig| sig.is_commit())
+                .filter_map(|sig| common_vals.validator(sig.validator_address()?))
+                .collect::<Vec<_>>()
+        } else if trusted.commit.round == conflicting_commit.round {
+            // The validator sets are the same, so the signatures of a
+            // validator have the same index in both commits.
+            conflicting_commit
+                .signatures
+                .iter()
+                .zip(&trusted.commit.signatures)
+                .filter(|(sig_a, sig_b)| sig_a.is_commit() && sig_b.is_commit())
+                .filter_map(|(sig_a, _)| {
+                    self.conflicting_block
+                        .validator_set
+                        .validator(sig_a.validator_address()?)
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        validators.sort_by_key(|v| (core::cmp::Reverse(v.power), v.address));
+        validators
+    }
+
+    /// Verify the evidence against the state of the chain.
+    ///
+    /// `common_header` is the trusted header at the common height, and
+    /// `common_vals` the validator set at that height. `trusted_header` is
+    /// the trusted header at the height of the conflicting block, or the
+    /// latest trusted header if the conflicting block is ahead of it.
+    #[cfg(feature = "rust-crypto")]
+    pub fn verify<V>(
+        &self,
+        verifier: &V,
+        common_header: &SignedHeader,
+        trusted_header: &SignedHeader,
+        common_vals: &validator::Set,
+    ) -> Result<(), Error>
+    where
+        V: Verifier,
+    {
+        self.verify_with::<crate::crypto::default::Sha256, V>(
+            verifier,
+            common_header,
+            trusted_header,
+            common_vals,
+        )
+    }
+
+    /// Verify the evidence with a Merkle hasher provided by a crypto
+    /// provider.
+    pub fn verify_with<H, V>(
+        &self,
+        verifier: &V,
+        common_header: &SignedHeader,
+        trusted_header: &SignedHeader,
+        common_vals: &validator::Set,
+    ) -> Result<(), Error>
+    where
+        H: MerkleHash + Sha256 + Default,
+        V: Verifier,
+    {
+        let conflicting = &self.conflicting_block.signed_header;
+        let chain_id = &trusted_header.header.chain_id;
+
+        // In a lunatic attack, a single verification jump is made from the
+        // common header to the conflicting one. Otherwise, the conflicting
+        // header is expected to be correctly derived.
+        if common_header.header.height != conflicting.header.height {
+            common_vals
+                .verify_commit_light_trusting(
+                    verifier,
+                    chain_id,
+                    &conflicting.commit,
+                    TrustThresholdFraction::ONE_THIRD,
+                )
+                .map_err(|e| {
+                    Error::evidence_verification(format!(
+                        "skipping verification of conflicting block failed: {}",
+                        e.detail()
+                    ))
+                })?;
+        } else if self.conflicting_header_is_invalid(&trusted_header.header) {
+            return Err(Error::evidence_verification(
+                "common height is the same as conflicting block height so expected the conflicting block to be correctly derived yet it wasn't".to_string(),
+            ));
+        }
+
+        // Verify that more than 2/3 of the conflicting validator set signed
+        // the conflicting header.
+        self.conflicting_block
+            .validator_set
+            .verify_commit_light(
+                verifier,
+                chain_id,
+                &conflicting.commit.block_id,
+                conflicting.header.height,
+                &conflicting.commit,
+            )
+            .map_err(|e| {
+                Error::evidence_verification(format!(
+                    "invalid commit from conflicting block: {}",
+                    e.detail()
+                ))
+            })?;
+
+        check_total_voting_power(self.total_voting_power, common_vals)?;
+
+        // A forward lunatic attack must violate monotonically increasing time.
+        if conflicting.header.height > trusted_header.header.height {
+            if conflicting.header.time > trusted_header.header.time {
+                return Err(Error::evidence_verification(format!(
+                    "conflicting block doesn't violate monotonically increasing time ({} is after {})",
+                    conflicting.header.time, trusted_header.header.time
+                )));
+            }
+        } else {
+            let trusted_hash = trusted_header.header.hash_with::<H>();
+            if trusted_hash == conflicting.header.hash_with::<H>() {
+                return Err(Error::evidence_verification(format!(
+                    "trusted header hash matches the evidence's conflicting header hash: {trusted_hash}"
+                )));
+            }
+        }
+
+        let validators = self.byzantine_validators(common_vals, trusted_header);
+        if validators.len() != self.byzantine_validators.len() {
+            return Err(Error::evidence_verification(format!(
+                "expected {} byzantine validators from evidence but got {}",
+                validators.len(),
+                self.byzantine_validators.len()
+            )));
+        }
+        for (expected, got) in validators.iter().zip(&self.byzantine_validators) {
+            if expected.address != got.address {
+                return Err(Error::evidence_verification(format!(
+                    "evidence contained an unexpected byzantine validator address; expected: {}, got: {}",
+                    expected.address, got.address
+                )));
+            }
+            if expected.power != got.power {
+                return Err(Error::evidence_verification(format!(
+                    "evidence contained unexpected byzantine validator power; expected {}, got {}",
+                    expected.power, got.power
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// A list of `Evidence`.
 ///
 /// <https://github.com/tendermint/spec/blob/d46cd7f573a2c6a2399fcab2cde981330aa63f37/spec/core/data_structures.md#evidencedata>
@@ -111,6 +589,22 @@ impl List {
     pub fn iter(&self) -> slice::Iter<'_, Evidence> {
         self.0.iter()
     }
+
+    /// Computes the Merkle root of the evidence, as committed to by the
+    /// `evidence_hash` of a block header.
+    #[cfg(feature = "rust-crypto")]
+    pub fn hash(&self) -> Hash {
+        self.hash_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Hash the evidence with a Merkle hasher provided by a crypto provider.
+    pub fn hash_with<H>(&self) -> Hash
+    where
+        H: MerkleHash + Default,
+    {
+        let evidence = self.0.iter().map(Evidence::hash_bytes).collect::<Vec<_>>();
+        Hash::Sha256(merkle::simple_hash_from_byte_vectors::<H>(&evidence))
+    }
 }
 
 impl AsRef<[Evidence]> for List {
@@ -214,7 +708,7 @@ tendermint_pb_modules! {
                 vote_a: Some(value.vote_a.into()),
                 vote_b: Some(value.vote_b.into()),
                 total_voting_power: value.total_voting_power.into(),
-                validator_power: value.total_voting_power.into(),
+                validator_power: value.validator_power.into(),
                 timestamp: Some(value.timestamp.into()),
             }
         }
@@ -380,3 +874,256 @@ impl From<Duration> for RawDuration {
         }
     }
 }
+
+#[cfg(all(test, feature = "rust-crypto"))]
+mod tests {
+    use super::*;
+    use crate::{
+        block::{parts::Header as PartSetHeader, Commit, CommitSig},
+        crypto::default::signature,
+        test::{Fixture, CHAIN_ID},
+        vote, AppHash,
+    };
+
+    /// A header at the given height for the validator set of the fixture,
+    /// signed by all the validators.
+    fn signed_header(fixture: &Fixture, height: u32, app_hash: u8) -> SignedHeader {
+        let mut header: block::Header = serde_json::from_str(include_str!(
+            "../tests/support/serialization/block/header.json"
+        ))
+        .unwrap();
+        header.chain_id = CHAIN_ID.parse().unwrap();
+        header.height = height.into();
+        header.validators_hash = fixture.validator_set.hash();
+        header.next_validators_hash = fixture.validator_set.hash();
+        header.app_hash = AppHash::try_from(vec![app_hash; 32]).unwrap();
+
+        let block_id = block::Id {
+            hash: header.hash(),
+            part_set_header: PartSetHeader::new(1, Hash::Sha256([1; 32])).unwrap(),
+        };
+        let signatures = (0..fixture.keys.len())
+            .map(|index| {
+                let vote =
+                    fixture.vote(index, vote::Type::Precommit, header.height, Some(block_id));
+                CommitSig::BlockIdFlagCommit {
+                    validator_address: vote.validator_address,
+                    timestamp: vote.timestamp.unwrap(),
+                    signature: vote.signature,
+                }
+            })
+            .collect();
+        let commit = Commit {
+            height: header.height,
+            round: 0u16.into(),
+            block_id,
+            signatures,
+        };
+        SignedHeader::new(header, commit).unwrap()
+    }
+
+    fn block_id(n: u8) -> block::Id {
+        block::Id {
+            hash: Hash::Sha256([n; 32]),
+            part_set_header: PartSetHeader::new(1, Hash::Sha256([n; 32])).unwrap(),
+        }
+    }
+
+    fn duplicate_vote_evidence(fixture: &Fixture) -> DuplicateVoteEvidence {
+        let height = 3u32.into();
+        DuplicateVoteEvidence::from_conflicting_votes(
+            fixture.vote(1, vote::Type::Precommit, height, Some(block_id(2))),
+            fixture.vote(1, vote::Type::Precommit, height, Some(block_id(1))),
+            Time::unix_epoch(),
+            &fixture.validator_set,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn verify_duplicate_vote() {
+        let fixture = Fixture::new(4);
+        let chain_id = CHAIN_ID.parse().unwrap();
+        let verifier = signature::Verifier;
+
+        let evidence = duplicate_vote_evidence(&fixture);
+        evidence.validate_basic().unwrap();
+        evidence
+            .verify(&verifier, &chain_id, &fixture.validator_set)
+            .unwrap();
+
+        // Votes in the wrong order.
+        let mut ev = evidence.clone();
+        core::mem::swap(&mut ev.vote_a, &mut ev.vote_b);
+        assert!(ev.validate_basic().is_err());
+
+        // Wrong voting powers.
+        let mut ev = evidence.clone();
+        ev.validator_power = 20u32.into();
+        assert!(ev
+            .verify(&verifier, &chain_id, &fixture.validator_set)
+            .is_err());
+        let mut ev = evidence.clone();
+        ev.total_voting_power = 30u32.into();
+        assert!(ev
+            .verify(&verifier, &chain_id, &fixture.validator_set)
+            .is_err());
+
+        // Votes for the same block.
+        let mut ev = evidence.clone();
+        ev.vote_b = ev.vote_a.clone();
+        assert!(ev
+            .verify(&verifier, &chain_id, &fixture.validator_set)
+            .is_err());
+
+        // Votes from different validators.
+        let mut ev = evidence.clone();
+        ev.vote_b = fixture.vote(2, vote::Type::Precommit, 3u32.into(), Some(block_id(2)));
+        assert!(ev
+            .verify(&verifier, &chain_id, &fixture.validator_set)
+            .is_err());
+
+        // Signatures for another chain.
+        assert!(evidence
+            .verify(
+                &verifier,
+                &"other-chain".parse().unwrap(),
+                &fixture.validator_set
+            )
+            .is_err());
+
+        // Validator not in the set.
+        let other = Fixture::new(2);
+        assert!(evidence
+            .verify(&verifier, &chain_id, &other.validator_set)
+            .is_err());
+    }
+
+    #[test]
+    fn verify_age() {
+        let fixture = Fixture::new(2);
+        let evidence = Evidence::from(duplicate_vote_evidence(&fixture));
+        let params = Params {
+            max_age_num_blocks: 10,
+            max_age_duration: Duration(core::time::Duration::from_secs(60)),
+            max_bytes: 0,
+        };
+        let time = |secs| Time::from_unix_timestamp(secs, 0).unwrap();
+
+        // The evidence is at height 3 and time 0.
+        evidence
+            .verify_age(&params, 13u32.into(), time(100))
+            .unwrap();
+        evidence
+            .verify_age(&params, 100u32.into(), time(60))
+            .unwrap();
+        assert!(evidence
+            .verify_age(&params, 14u32.into(), time(61))
+            .is_err());
+    }
+
+    #[test]
+    fn verify_lunatic_attack() {
+        let fixture = Fixture::new(4);
+        let verifier = signature::Verifier;
+
+        let common_header = signed_header(&fixture, 9, 0);
+        let trusted_header = signed_header(&fixture, 10, 0);
+        let conflicting_header = signed_header(&fixture, 10, 1);
+
+        let evidence = LightClientAttackEvidence {
+            conflicting_block: ConflictingBlock {
+                signed_header: conflicting_header,
+                validator_set: fixture.validator_set.clone(),
+            },
+            common_height: 9u32.into(),
+            byzantine_validators: fixture.validator_set.validators().clone(),
+            total_voting_power: 40u32.into(),
+            timestamp: common_header.header.time,
+        };
+        evidence.validate_basic().unwrap();
+        assert!(evidence.conflicting_header_is_invalid(&trusted_header.header));
+        evidence
+            .verify(
+                &verifier,
+                &common_header,
+                &trusted_header,
+                &fixture.validator_set,
+            )
+            .unwrap();
+
+        // Missing byzantine validator.
+        let mut ev = evidence.clone();
+        ev.byzantine_validators.pop();
+        assert!(ev
+            .verify(
+                &verifier,
+                &common_header,
+                &trusted_header,
+                &fixture.validator_set
+            )
+            .is_err());
+
+        // Wrong total voting power.
+        let mut ev = evidence.clone();
+        ev.total_voting_power = 50u32.into();
+        assert!(ev
+            .verify(
+                &verifier,
+                &common_header,
+                &trusted_header,
+                &fixture.validator_set
+            )
+            .is_err());
+
+        // The conflicting block is the trusted block.
+        let mut ev = evidence.clone();
+        ev.conflicting_block.signed_header = trusted_header.clone();
+        assert!(ev
+            .verify(
+                &verifier,
+                &common_header,
+                &trusted_header,
+                &fixture.validator_set
+            )
+            .is_err());
+
+        // Inconsistent conflicting block.
+        let mut ev = evidence.clone();
+        ev.conflicting_block.signed_header.header.app_hash = AppHash::default();
+        assert!(ev.validate_basic().is_err());
+        let mut ev = evidence;
+        ev.common_height = 11u32.into();
+        assert!(ev.validate_basic().is_err());
+    }
+
+    #[test]
+    fn equivocation_byzantine_validators() {
+        let fixture = Fixture::new(3);
+        let trusted_header = signed_header(&fixture, 10, 0);
+        let mut conflicting_header = signed_header(&fixture, 10, 0);
+        conflicting_header.header.time = Time::unix_epoch();
+        conflicting_header.commit.signatures[0] = CommitSig::BlockIdFlagAbsent;
+
+        let evidence = LightClientAttackEvidence {
+            conflicting_block: ConflictingBlock {
+                signed_header: conflicting_header,
+                validator_set: fixture.validator_set.clone(),
+            },
+            common_height: 10u32.into(),
+            byzantine_validators: vec![],
+            total_voting_power: 30u32.into(),
+            timestamp: trusted_header.header.time,
+        };
+        assert!(!evidence.conflicting_header_is_invalid(&trusted_header.header));
+        let validators = evidence.byzantine_validators(&fixture.validator_set, &trusted_header);
+        assert_eq!(validators, fixture.validator_set.validators()[1..]);
+
+        // Commits in different rounds are an amnesia attack.
+        let mut trusted_header = trusted_header;
+        trusted_header.commit.round = 1u16.into();
+        assert!(evidence
+            .byzantine_validators(&fixture.validator_set, &trusted_header)
+            .is_empty());
+    }
+}
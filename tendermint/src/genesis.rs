@@ -1,8 +1,13 @@
 //! Genesis data
 
+use alloc::collections::BTreeSet;
+
 use serde::{Deserialize, Serialize};
 
-use crate::{chain, consensus, prelude::*, serializers, validator, AppHash, Time};
+use crate::{
+    account, chain, consensus, crypto::Sha256, error::Error, merkle::MerkleHash, prelude::*,
+    serializers, validator, AppHash, Hash, PublicKey, Time,
+};
 
 /// Genesis data
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -21,7 +26,7 @@ pub struct Genesis<AppState = serde_json::Value> {
     pub consensus_params: consensus::Params,
 
     /// Validators
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub validators: Vec<validator::Info>,
 
     /// App hash
@@ -31,3 +36,195 @@ pub struct Genesis<AppState = serde_json::Value> {
     /// App state
     pub app_state: AppState,
 }
+
+impl<AppState> Genesis<AppState> {
+    /// Check the genesis data for values that would prevent a chain from
+    /// starting, as CometBFT does when loading a genesis file.
+    #[cfg(feature = "rust-crypto")]
+    pub fn validate(&self) -> Result<(), Error> {
+        self.validate_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Check the genesis data with a hasher provided by a crypto provider,
+    /// which is used to derive the validator addresses.
+    pub fn validate_with<H>(&self) -> Result<(), Error>
+    where
+        H: MerkleHash + Sha256,
+    {
+        let invalid = |reason: String| Err(Error::invalid_genesis(reason));
+
+        if self.chain_id.as_str().is_empty() {
+            return invalid("chain ID must not be empty".into());
+        }
+        if self.chain_id.as_str().len() > chain::id::MAX_LENGTH {
+            return invalid(format!(
+                "chain ID is too long: {} > {}",
+                self.chain_id.as_str().len(),
+                chain::id::MAX_LENGTH
+            ));
+        }
+        if self.initial_height < 0 {
+            return invalid(format!(
+                "initial height must not be negative, got {}",
+                self.initial_height
+            ));
+        }
+        self.consensus_params.validate_basic()?;
+
+        let mut addresses = BTreeSet::new();
+        let mut total_power = 0u64;
+        for (i, validator) in self.validators.iter().enumerate() {
+            if validator.power() == 0 {
+                return invalid(format!("validator #{i} has no voting power"));
+            }
+            let address = validator_address::<H>(validator.pub_key);
+            if validator.address != address {
+                return invalid(format!(
+                    "incorrect address for validator #{i}: {}, should be {}",
+                    validator.address, address
+                ));
+            }
+            if !addresses.insert(validator.address) {
+                return invalid(format!("duplicate validator address {}", validator.address));
+            }
+            total_power = total_power.saturating_add(validator.power());
+        }
+        if total_power > validator::Set::MAX_TOTAL_VOTING_POWER {
+            return invalid(format!(
+                "total voting power of validators is too big: {} > {}",
+                total_power,
+                validator::Set::MAX_TOTAL_VOTING_POWER
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The address of a validator with the given public key.
+fn validator_address<H: Sha256>(pub_key: PublicKey) -> account::Id {
+    let mut bytes = [0u8; account::LENGTH];
+    match pub_key {
+        // RIPEMD160(SHA256(pk))
+        #[cfg(feature = "secp256k1")]
+        PublicKey::Secp256k1(pk) => {
+            use ripemd::{Digest, Ripemd160};
+
+            bytes.copy_from_slice(&Ripemd160::digest(H::digest(pk.to_sec1_bytes())));
+        },
+        // SHA256(pk)[:20]
+        _ => bytes.copy_from_slice(&H::digest(pub_key.to_bytes())[..account::LENGTH]),
+    }
+    account::Id::new(bytes)
+}
+
+/// Compute the hash of a genesis file, as CometBFT does to check the genesis
+/// file against the one a node was started with.
+///
+/// CometBFT hashes the contents of the file as they were read, before
+/// decoding them. The hash cannot be computed from a decoded [`Genesis`], as
+/// its encoding need not reproduce the formatting of the file, nor, for an
+/// application state of a type other than [`serde_json::Value`], the
+/// application state itself. It is therefore computed from the raw bytes of
+/// the file, whatever the type of its application state.
+#[cfg(feature = "rust-crypto")]
+pub fn hash_file(bytes: &[u8]) -> Hash {
+    hash_file_with::<crate::crypto::default::Sha256>(bytes)
+}
+
+/// Compute the hash of a genesis file with a hasher provided by a crypto
+/// provider.
+pub fn hash_file_with<H: Sha256>(bytes: &[u8]) -> Hash {
+    Hash::Sha256(H::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genesis() -> Genesis {
+        serde_json::from_str(include_str!("../tests/support/genesis/genesis.json")).unwrap()
+    }
+
+    #[cfg(feature = "rust-crypto")]
+    #[test]
+    fn validates_genesis() {
+        genesis().validate().unwrap();
+
+        let mut invalid = genesis();
+        invalid.initial_height = -1;
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = genesis();
+        invalid.consensus_params.block.max_bytes = 0;
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = genesis();
+        invalid.validators[0].power = 0u32.into();
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = genesis();
+        invalid.validators.push(invalid.validators[0].clone());
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = genesis();
+        let pub_key = PublicKey::from_raw_ed25519(&[1; 32]).unwrap();
+        let power = validator::Set::MAX_TOTAL_VOTING_POWER.try_into().unwrap();
+        invalid
+            .validators
+            .push(validator::Info::new(pub_key, power));
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn json_omits_empty_validators() {
+        let mut genesis = genesis();
+        genesis.validators.clear();
+        let json = serde_json::to_value(&genesis).unwrap();
+        assert!(json.get("validators").is_none());
+        let parsed: Genesis = serde_json::from_value(json).unwrap();
+        assert!(parsed.validators.is_empty());
+    }
+
+    #[cfg(feature = "rust-crypto")]
+    #[test]
+    fn rejects_incorrect_validator_address() {
+        let mut invalid = genesis();
+        invalid.validators[0].address = account::Id::new([0; 20]);
+        assert!(invalid.validate().is_err());
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn derives_validator_addresses_as_account_ids() {
+        use crate::crypto::default::Sha256;
+
+        let ed25519 = PublicKey::from_raw_ed25519(&[1; 32]).unwrap();
+        let secp256k1 = PublicKey::from_raw_secp256k1(
+            &subtle_encoding::hex::decode_upper(
+                "02A1633CAFCC01EBFB6D78E39F687A1F0995C62FC95F51EAD10A02EE0BE551B5DC",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        for pub_key in [ed25519, secp256k1] {
+            assert_eq!(
+                validator_address::<Sha256>(pub_key),
+                account::Id::from(pub_key)
+            );
+        }
+    }
+
+    #[cfg(feature = "rust-crypto")]
+    #[test]
+    fn hashes_genesis_file_like_cometbft() {
+        // sha256sum tests/support/genesis/genesis.json
+        let expected = Hash::from_hex_upper(
+            crate::hash::Algorithm::Sha256,
+            "EF1D15C8C6E167B9CCE6EE522E0ABFD0273B59D2DB856530394E159091F472F7",
+        )
+        .unwrap();
+        let bytes = include_bytes!("../tests/support/genesis/genesis.json");
+        assert_eq!(hash_file(bytes), expected);
+    }
+}
@@ -1,8 +1,10 @@
 //! Merkle tree used in Tendermint networks
 
 pub mod proof;
+pub mod proof_op;
 
 pub use proof::Proof;
+pub use proof_op::{ProofOperator, ProofRuntime};
 
 use core::marker::PhantomData;
 
@@ -28,6 +30,64 @@ where
     hasher.hash_byte_vectors(byte_vecs)
 }
 
+/// Compute a simple Merkle root from vectors of arbitrary byte vectors,
+/// along with a proof of inclusion for each of them.
+///
+/// The proofs are in the same order as the byte vectors.
+pub fn proofs_from_byte_vectors<H>(byte_vecs: &[impl AsRef<[u8]>]) -> (Hash, Vec<Proof>)
+where
+    H: MerkleHash + Default,
+{
+    let mut hasher = H::default();
+    let mut trails = vec![(Hash::default(), Vec::new()); byte_vecs.len()];
+    let root = hash_byte_vectors_with_trails(&mut hasher, byte_vecs, &mut trails);
+
+    let total = byte_vecs.len() as u64;
+    let proofs = trails
+        .into_iter()
+        .zip(0..)
+        .map(|((leaf_hash, aunts), index)| Proof {
+            total,
+            index,
+            leaf_hash: crate::Hash::Sha256(leaf_hash),
+            aunts: aunts.into_iter().map(crate::Hash::Sha256).collect(),
+        })
+        .collect();
+    (root, proofs)
+}
+
+// Like `MerkleHash::hash_byte_vectors`, but also records the leaf hash
+// and the aunts of each leaf in `trails`, aunts ordered from the leaf's
+// sibling up to a child of the root.
+fn hash_byte_vectors_with_trails<H: MerkleHash>(
+    hasher: &mut H,
+    byte_vecs: &[impl AsRef<[u8]>],
+    trails: &mut [(Hash, Vec<Hash>)],
+) -> Hash {
+    let length = byte_vecs.len();
+    match length {
+        0 => hasher.empty_hash(),
+        1 => {
+            let leaf_hash = hasher.leaf_hash(byte_vecs[0].as_ref());
+            trails[0].0 = leaf_hash;
+            leaf_hash
+        },
+        _ => {
+            let split = length.next_power_of_two() / 2;
+            let (left_trails, right_trails) = trails.split_at_mut(split);
+            let left = hash_byte_vectors_with_trails(hasher, &byte_vecs[..split], left_trails);
+            let right = hash_byte_vectors_with_trails(hasher, &byte_vecs[split..], right_trails);
+            for (_, aunts) in left_trails {
+                aunts.push(right);
+            }
+            for (_, aunts) in right_trails {
+                aunts.push(left);
+            }
+            hasher.inner_hash(left, right)
+        },
+    }
+}
+
 /// Implementation of Merkle tree hashing for Tendermint.
 pub trait MerkleHash {
     // tmhash({})
@@ -192,9 +252,64 @@ mod tests {
         assert_eq!(node_hash, &hash);
     }
 
+    #[test]
+    fn test_proofs_from_byte_vectors() {
+        for total in 0..=17 {
+            let items = (0..total).map(|i| vec![i as u8; i]).collect::<Vec<_>>();
+            let (root, proofs) = proofs_from_byte_vectors::<Sha256>(&items);
+
+            assert_eq!(root, simple_hash_from_byte_vectors::<Sha256>(&items));
+            assert_eq!(proofs.len(), total);
+            for (i, (proof, item)) in proofs.iter().zip(&items).enumerate() {
+                assert_eq!(proof.total, total as u64);
+                assert_eq!(proof.index, i as u64);
+                proof
+                    .verify_with::<Sha256>(&crate::Hash::Sha256(root), item)
+                    .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_proofs_from_byte_vectors_aunts() {
+        let items = [b"a", b"b", b"c"];
+        let (_, proofs) = proofs_from_byte_vectors::<Sha256>(&items);
+
+        let mut hasher = Sha256::default();
+        let [a, b, c] = items.map(|item| hasher.leaf_hash(item));
+        let ab = hasher.inner_hash(a, b);
+
+        let aunts = |proof: &Proof| {
+            proof
+                .aunts
+                .iter()
+                .map(|aunt| aunt.as_bytes().to_vec())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(aunts(&proofs[0]), [b.to_vec(), c.to_vec()]);
+        assert_eq!(aunts(&proofs[1]), [a.to_vec(), c.to_vec()]);
+        assert_eq!(aunts(&proofs[2]), [ab.to_vec()]);
+    }
+
     mod non_incremental {
         use super::*;
 
+        #[test]
+        fn test_proofs_from_byte_vectors() {
+            let items = (0..7).map(|i| vec![i as u8; i]).collect::<Vec<_>>();
+            let (root, proofs) = proofs_from_byte_vectors::<NonIncremental<Sha256>>(&items);
+
+            assert_eq!(
+                proofs_from_byte_vectors::<Sha256>(&items),
+                (root, proofs.clone())
+            );
+            for (proof, item) in proofs.iter().zip(&items) {
+                proof
+                    .verify_with::<NonIncremental<Sha256>>(&crate::Hash::Sha256(root), item)
+                    .unwrap();
+            }
+        }
+
         #[test]
         fn test_rfc6962_tree_of_2() {
             let node_hash_hex = "dc9a0536ff2e196d5a628a5bf377ab247bbddf83342be39699461c1e766e6646";
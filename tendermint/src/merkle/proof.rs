@@ -3,7 +3,8 @@
 use serde::{Deserialize, Serialize};
 use tendermint_proto::v0_37::crypto::Proof as RawProof;
 
-use crate::{prelude::*, serializers, Hash};
+use super::MerkleHash;
+use crate::{prelude::*, serializers, Error, Hash};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(try_from = "RawProof", into = "RawProof")]
@@ -18,6 +19,100 @@ pub struct Proof {
     pub aunts: Vec<Hash>,
 }
 
+impl Proof {
+    /// Verify that this proof attests to the inclusion of `leaf` in the
+    /// Merkle tree with the given root hash.
+    #[cfg(feature = "rust-crypto")]
+    pub fn verify(&self, root_hash: &Hash, leaf: &[u8]) -> Result<(), Error> {
+        self.verify_with::<crate::crypto::default::Sha256>(root_hash, leaf)
+    }
+
+    /// Verify this proof with a Merkle hasher provided by a crypto provider.
+    pub fn verify_with<H>(&self, root_hash: &Hash, leaf: &[u8]) -> Result<(), Error>
+    where
+        H: MerkleHash + Default,
+    {
+        let leaf_hash = H::default().leaf_hash(leaf);
+        if self.leaf_hash.as_bytes() != leaf_hash {
+            return Err(Error::invalid_merkle_proof(format!(
+                "invalid leaf hash: wanted {} got {}",
+                Hash::Sha256(leaf_hash),
+                self.leaf_hash
+            )));
+        }
+        let computed_hash = self.compute_root_hash_with::<H>()?;
+        if root_hash != &computed_hash {
+            return Err(Error::invalid_merkle_proof(format!(
+                "invalid root hash: wanted {root_hash} got {computed_hash}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Compute the root hash of the Merkle tree from the leaf hash and the
+    /// aunts in this proof.
+    pub fn compute_root_hash_with<H>(&self) -> Result<Hash, Error>
+    where
+        H: MerkleHash + Default,
+    {
+        let Hash::Sha256(leaf_hash) = self.leaf_hash else {
+            return Err(Error::invalid_merkle_proof("empty leaf hash".to_string()));
+        };
+        let aunts = self
+            .aunts
+            .iter()
+            .map(|aunt| match aunt {
+                Hash::Sha256(hash) => Ok(*hash),
+                Hash::None => Err(Error::invalid_merkle_proof("empty aunt hash".to_string())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        compute_hash_from_aunts(&mut H::default(), self.index, self.total, leaf_hash, &aunts)
+            .map(Hash::Sha256)
+    }
+}
+
+/// Recursively compute the root hash of a subtree of `total` leaves from the
+/// hash of the leaf at `index` and the hashes of its aunts, ordered from the
+/// leaf's sibling up to a child of the subtree root.
+fn compute_hash_from_aunts<H: MerkleHash>(
+    hasher: &mut H,
+    index: u64,
+    total: u64,
+    leaf_hash: super::Hash,
+    aunts: &[super::Hash],
+) -> Result<super::Hash, Error> {
+    if index >= total {
+        return Err(Error::invalid_merkle_proof(format!(
+            "invalid index {index} for a tree of {total} items"
+        )));
+    }
+    match (total, aunts.split_last()) {
+        (1, None) => Ok(leaf_hash),
+        (1, Some(_)) => Err(Error::invalid_merkle_proof(
+            "unexpected inner hashes".to_string(),
+        )),
+        (_, None) => Err(Error::invalid_merkle_proof(
+            "expected at least one inner hash".to_string(),
+        )),
+        (_, Some((last, rest))) => {
+            let num_left = total.next_power_of_two() / 2;
+            if index < num_left {
+                let left = compute_hash_from_aunts(hasher, index, num_left, leaf_hash, rest)?;
+                Ok(hasher.inner_hash(left, *last))
+            } else {
+                let right = compute_hash_from_aunts(
+                    hasher,
+                    index - num_left,
+                    total - num_left,
+                    leaf_hash,
+                    rest,
+                )?;
+                Ok(hasher.inner_hash(*last, right))
+            }
+        },
+    }
+}
+
 /// Merkle proof defined by the list of ProofOps
 /// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/crypto/merkle/merkle.proto#L26>
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
@@ -141,7 +236,68 @@ tendermint_pb_modules! {
 #[cfg(test)]
 mod test {
     use super::ProofOps;
-    use crate::test::test_serialization_roundtrip;
+    use crate::{prelude::*, test::test_serialization_roundtrip};
+
+    #[cfg(feature = "rust-crypto")]
+    mod crypto {
+        use crate::{
+            merkle::{MerkleHash, Proof},
+            prelude::*,
+            Hash,
+        };
+
+        /// Proofs for each leaf of the tree over `b"a"`, `b"b"` and `b"c"`,
+        /// along with its root hash.
+        fn three_leaf_proofs() -> (Hash, Vec<Proof>) {
+            let mut hasher = sha2::Sha256::default();
+            let [a, b, c] = [b"a", b"b", b"c"].map(|leaf| hasher.leaf_hash(leaf));
+            let ab = hasher.inner_hash(a, b);
+            let root = hasher.inner_hash(ab, c);
+
+            let proof = |index, leaf_hash, aunts: &[[u8; 32]]| Proof {
+                total: 3,
+                index,
+                leaf_hash: Hash::Sha256(leaf_hash),
+                aunts: aunts.iter().copied().map(Hash::Sha256).collect(),
+            };
+            let proofs = vec![
+                proof(0, a, &[b, c]),
+                proof(1, b, &[a, c]),
+                proof(2, c, &[ab]),
+            ];
+            (Hash::Sha256(root), proofs)
+        }
+
+        #[test]
+        fn verify_proofs() {
+            let (root, proofs) = three_leaf_proofs();
+            for (proof, leaf) in proofs.iter().zip([b"a", b"b", b"c"]) {
+                proof.verify(&root, leaf).unwrap();
+            }
+        }
+
+        #[test]
+        fn verify_rejects_invalid_proofs() {
+            let (root, proofs) = three_leaf_proofs();
+
+            // Wrong leaf.
+            assert!(proofs[0].verify(&root, b"b").is_err());
+            // Wrong root.
+            assert!(proofs[0].verify(&Hash::Sha256([0; 32]), b"a").is_err());
+            // Index out of range.
+            let mut proof = proofs[2].clone();
+            proof.index = 3;
+            assert!(proof.verify(&root, b"c").is_err());
+            // Missing aunt.
+            let mut proof = proofs[0].clone();
+            proof.aunts.pop();
+            assert!(proof.verify(&root, b"a").is_err());
+            // Extra aunt.
+            let mut proof = proofs[2].clone();
+            proof.aunts.insert(0, proofs[0].leaf_hash);
+            assert!(proof.verify(&root, b"c").is_err());
+        }
+    }
 
     #[test]
     fn serialization_roundtrip() {
@@ -0,0 +1,343 @@
+//! Verification of chained Merkle proofs, as returned by `abci_query`.
+//!
+//! Each [`ProofOp`] in a [`ProofOps`] list is decoded by a [`ProofRuntime`]
+//! into a [`ProofOperator`]. The operators are then run in order, each one
+//! computing the root of its own tree from the output of the previous one,
+//! until the last one produces the root that is checked against the app hash.
+//!
+//! Based on <https://github.com/cometbft/cometbft/blob/v0.38.x/crypto/merkle/proof_op.go>
+
+use alloc::collections::BTreeMap;
+use core::marker::PhantomData;
+
+use prost::Message;
+use tendermint_proto::v0_37::crypto::ValueOp as RawValueOp;
+
+use super::{
+    proof::{ProofOp, ProofOps},
+    MerkleHash, Proof,
+};
+use crate::{crypto::Sha256, prelude::*, Error, Hash};
+
+/// A single step of a chained Merkle proof.
+pub trait ProofOperator {
+    /// Compute the output of this step from the output of the previous one.
+    ///
+    /// The first step is given the value being proven, or nothing for an
+    /// absence proof.
+    fn run(&self, args: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>, Error>;
+
+    /// The key proven by this step, or an empty slice if it proves no key.
+    fn key(&self) -> &[u8];
+
+    /// Encode this step as a [`ProofOp`].
+    fn proof_op(&self) -> ProofOp;
+}
+
+/// Decodes a [`ProofOp`] of a given type into a [`ProofOperator`].
+pub type ProofOpDecoder = fn(&ProofOp) -> Result<Box<dyn ProofOperator>, Error>;
+
+/// A registry of [`ProofOpDecoder`]s, keyed by [`ProofOp::field_type`].
+#[derive(Clone, Debug)]
+pub struct ProofRuntime {
+    decoders: BTreeMap<String, ProofOpDecoder>,
+}
+
+impl ProofRuntime {
+    /// Create a runtime without any decoders registered.
+    pub fn new() -> Self {
+        Self {
+            decoders: BTreeMap::new(),
+        }
+    }
+
+    /// Register a decoder for proof ops of the given type.
+    ///
+    /// Returns the previously registered decoder for that type, if any.
+    pub fn register_op_decoder(
+        &mut self,
+        field_type: impl Into<String>,
+        decoder: ProofOpDecoder,
+    ) -> Option<ProofOpDecoder> {
+        self.decoders.insert(field_type.into(), decoder)
+    }
+
+    /// Decode a single proof op.
+    pub fn decode(&self, op: &ProofOp) -> Result<Box<dyn ProofOperator>, Error> {
+        let decoder = self
+            .decoders
+            .get(&op.field_type)
+            .ok_or_else(|| Error::unrecognized_proof_op(op.field_type.clone()))?;
+        decoder(op)
+    }
+
+    /// Decode every op of a chained proof.
+    pub fn decode_proof(&self, proof: &ProofOps) -> Result<Vec<Box<dyn ProofOperator>>, Error> {
+        proof.ops.iter().map(|op| self.decode(op)).collect()
+    }
+
+    /// Verify that `proof` attests to `value` being stored under `keys` in
+    /// the state with the given root hash.
+    ///
+    /// The keys are ordered from the outermost store to the innermost key,
+    /// e.g. `[b"ibc", b"clients/07-tendermint-0/clientState"]`.
+    pub fn verify_value(
+        &self,
+        proof: &ProofOps,
+        root: &[u8],
+        keys: &[impl AsRef<[u8]>],
+        value: &[u8],
+    ) -> Result<(), Error> {
+        self.verify(proof, root, keys, vec![value.to_vec()])
+    }
+
+    /// Verify that `proof` attests to no value being stored under `keys` in
+    /// the state with the given root hash.
+    pub fn verify_absence(
+        &self,
+        proof: &ProofOps,
+        root: &[u8],
+        keys: &[impl AsRef<[u8]>],
+    ) -> Result<(), Error> {
+        self.verify(proof, root, keys, Vec::new())
+    }
+
+    /// Run the ops of `proof` on `args` and check that they consume all
+    /// `keys` and produce the given root hash.
+    pub fn verify(
+        &self,
+        proof: &ProofOps,
+        root: &[u8],
+        keys: &[impl AsRef<[u8]>],
+        mut args: Vec<Vec<u8>>,
+    ) -> Result<(), Error> {
+        let mut keys = keys.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+        for (i, op) in self.decode_proof(proof)?.iter().enumerate() {
+            let key = op.key();
+            if !key.is_empty() {
+                match keys.pop() {
+                    Some(last_key) if last_key == key => {},
+                    Some(last_key) => {
+                        return Err(Error::invalid_merkle_proof(format!(
+                            "key mismatch on operation #{i}: expected {} but got {}",
+                            String::from_utf8_lossy(last_key),
+                            String::from_utf8_lossy(key)
+                        )))
+                    },
+                    None => {
+                        return Err(Error::invalid_merkle_proof(format!(
+                            "key path has insufficient number of parts: expected no more keys but got {}",
+                            String::from_utf8_lossy(key)
+                        )))
+                    },
+                }
+            }
+            args = op.run(args)?;
+        }
+
+        match args.first() {
+            Some(computed) if computed.as_slice() == root => {},
+            _ => {
+                return Err(Error::invalid_merkle_proof(
+                    "calculated root hash is invalid".to_string(),
+                ))
+            },
+        }
+        if !keys.is_empty() {
+            return Err(Error::invalid_merkle_proof(
+                "key path not consumed all".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Creates a runtime that decodes [`ValueOp`]s.
+#[cfg(feature = "rust-crypto")]
+impl Default for ProofRuntime {
+    fn default() -> Self {
+        let mut runtime = Self::new();
+        runtime.register_op_decoder(
+            PROOF_OP_VALUE,
+            ValueOp::<crate::crypto::default::Sha256>::decode,
+        );
+        runtime
+    }
+}
+
+/// The [`ProofOp::field_type`] of a [`ValueOp`].
+pub const PROOF_OP_VALUE: &str = "simple:v";
+
+/// Proves that a key-value pair is a leaf of a simple Merkle tree.
+///
+/// The leaf is the length-prefixed key followed by the length-prefixed
+/// SHA-256 hash of the value. The output is the root of the tree.
+pub struct ValueOp<H> {
+    key: Vec<u8>,
+    proof: Proof,
+    _hasher: PhantomData<H>,
+}
+
+impl<H> ValueOp<H> {
+    pub fn new(key: Vec<u8>, proof: Proof) -> Self {
+        Self {
+            key,
+            proof,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// The Merkle proof of the key-value pair.
+    pub fn proof(&self) -> &Proof {
+        &self.proof
+    }
+}
+
+impl<H> ValueOp<H>
+where
+    H: MerkleHash + Sha256 + Default + 'static,
+{
+    /// Decode a [`ProofOp`] of type [`PROOF_OP_VALUE`].
+    ///
+    /// Can be registered as a [`ProofOpDecoder`].
+    pub fn decode(op: &ProofOp) -> Result<Box<dyn ProofOperator>, Error> {
+        if op.field_type != PROOF_OP_VALUE {
+            return Err(Error::unrecognized_proof_op(op.field_type.clone()));
+        }
+        let raw = RawValueOp::decode(op.data.as_slice())
+            .map_err(|e| Error::invalid_merkle_proof(format!("decoding value op: {e}")))?;
+        let proof = raw.proof.ok_or_else(Error::missing_data)?.try_into()?;
+        Ok(Box::new(Self::new(op.key.clone(), proof)))
+    }
+}
+
+impl<H> ProofOperator for ValueOp<H>
+where
+    H: MerkleHash + Sha256 + Default,
+{
+    fn run(&self, args: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>, Error> {
+        let [value] = args.as_slice() else {
+            return Err(Error::invalid_merkle_proof(format!(
+                "expected 1 arg, got {}",
+                args.len()
+            )));
+        };
+        let value_hash = H::digest(value);
+
+        let mut leaf = Vec::with_capacity(self.key.len() + value_hash.len() + 2);
+        prost::encoding::encode_varint(self.key.len() as u64, &mut leaf);
+        leaf.extend_from_slice(&self.key);
+        prost::encoding::encode_varint(value_hash.len() as u64, &mut leaf);
+        leaf.extend_from_slice(&value_hash);
+
+        let leaf_hash = H::default().leaf_hash(&leaf);
+        if self.proof.leaf_hash != Hash::Sha256(leaf_hash) {
+            return Err(Error::invalid_merkle_proof(format!(
+                "leaf hash mismatch: want {} got {}",
+                self.proof.leaf_hash,
+                Hash::Sha256(leaf_hash)
+            )));
+        }
+
+        let root_hash = self.proof.compute_root_hash_with::<H>()?;
+        Ok(vec![root_hash.as_bytes().to_vec()])
+    }
+
+    fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    fn proof_op(&self) -> ProofOp {
+        let raw = RawValueOp {
+            key: self.key.clone(),
+            proof: Some(self.proof.clone().into()),
+        };
+        ProofOp {
+            field_type: PROOF_OP_VALUE.to_string(),
+            key: self.key.clone(),
+            data: raw.encode_to_vec(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rust-crypto"))]
+mod tests {
+    use super::*;
+
+    type DefaultValueOp = ValueOp<crate::crypto::default::Sha256>;
+
+    /// A proof of `key` = `value` in a store holding a single key-value
+    /// pair, along with the store's root hash.
+    fn single_value_proof(key: &[u8], value: &[u8]) -> (Vec<u8>, ProofOps) {
+        use crate::crypto::Sha256 as _;
+
+        let mut leaf = vec![key.len() as u8];
+        leaf.extend_from_slice(key);
+        leaf.push(32);
+        leaf.extend_from_slice(&crate::crypto::default::Sha256::digest(value));
+        let leaf_hash = sha2::Sha256::default().leaf_hash(&leaf);
+
+        let op = DefaultValueOp::new(
+            key.to_vec(),
+            Proof {
+                total: 1,
+                index: 0,
+                leaf_hash: Hash::Sha256(leaf_hash),
+                aunts: vec![],
+            },
+        );
+        let proof = ProofOps {
+            ops: vec![op.proof_op()],
+        };
+        (leaf_hash.to_vec(), proof)
+    }
+
+    #[test]
+    fn verify_value() {
+        let (root, proof) = single_value_proof(b"key", b"value");
+        ProofRuntime::default()
+            .verify_value(&proof, &root, &[b"key"], b"value")
+            .unwrap();
+    }
+
+    #[test]
+    fn verify_value_rejects_wrong_value() {
+        let (root, proof) = single_value_proof(b"key", b"value");
+        let runtime = ProofRuntime::default();
+
+        assert!(runtime
+            .verify_value(&proof, &root, &[b"key"], b"other")
+            .is_err());
+        assert!(runtime
+            .verify_value(&proof, &root, &[b"other"], b"value")
+            .is_err());
+        assert!(runtime
+            .verify_value(&proof, &[0; 32], &[b"key"], b"value")
+            .is_err());
+    }
+
+    #[test]
+    fn verify_value_rejects_unconsumed_keys() {
+        let (root, proof) = single_value_proof(b"key", b"value");
+        let keys: [&[u8]; 2] = [b"store", b"key"];
+        assert!(ProofRuntime::default()
+            .verify_value(&proof, &root, &keys, b"value")
+            .is_err());
+    }
+
+    #[test]
+    fn unregistered_op_type() {
+        let (root, proof) = single_value_proof(b"key", b"value");
+        assert!(ProofRuntime::new()
+            .verify_value(&proof, &root, &[b"key"], b"value")
+            .is_err());
+    }
+
+    #[test]
+    fn value_op_round_trip() {
+        let (_, proof) = single_value_proof(b"key", b"value");
+        let op = DefaultValueOp::decode(&proof.ops[0]).unwrap();
+        assert_eq!(op.key(), b"key");
+        assert_eq!(op.proof_op(), proof.ops[0]);
+    }
+}
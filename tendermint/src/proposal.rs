@@ -15,8 +15,9 @@ use crate::{
     block::{Height, Id as BlockId, Round},
     chain::Id as ChainId,
     consensus::State,
+    crypto::signature::{Signer, Verifier},
     prelude::*,
-    Signature, Time,
+    validator, Error, Signature, Time,
 };
 
 /// Proposal
@@ -104,6 +105,85 @@ impl Proposal {
         Protobuf::<RawCanonicalProposal>::encode_length_delimited_vec(canonical)
     }
 
+    /// Sign the proposal with the given signer and store the resulting signature
+    /// in the `signature` field.
+    pub fn sign<S>(&mut self, chain_id: ChainId, signer: &S) -> Result<(), Error>
+    where
+        S: Signer + ?Sized,
+    {
+        let sign_bytes = self.clone().into_signable_vec(chain_id);
+        let signature = signer.sign(&sign_bytes).map_err(Error::signing)?;
+        self.signature = Some(signature);
+        Ok(())
+    }
+
+    /// Perform basic validation of the proposal, without verifying its
+    /// signature.
+    ///
+    /// The message type is enforced by [`Type`] when decoding.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        let invalid = |reason: String| Err(Error::invalid_proposal(reason));
+
+        if let Some(pol_round) = self.pol_round {
+            if pol_round >= self.round {
+                return invalid(format!(
+                    "POL round {} must be less than round {}",
+                    pol_round, self.round
+                ));
+            }
+        }
+        match &self.block_id {
+            Some(block_id)
+                if !block_id.hash.is_empty()
+                    && block_id.part_set_header.total > 0
+                    && !block_id.part_set_header.hash.is_empty() => {},
+            _ => return invalid("expected a complete block ID".into()),
+        }
+        if self.timestamp.is_none() {
+            return invalid("missing timestamp".into());
+        }
+        if self.signature.is_none() {
+            return Err(Error::empty_signature());
+        }
+        Ok(())
+    }
+
+    /// Verify the signature of the proposal against the public key of the
+    /// given proposer.
+    pub fn verify<V>(
+        &self,
+        verifier: &V,
+        chain_id: &ChainId,
+        proposer: &validator::Info,
+    ) -> Result<(), Error>
+    where
+        V: Verifier,
+    {
+        let signature = self.signature.as_ref().ok_or_else(Error::empty_signature)?;
+        let sign_bytes = self.clone().into_signable_vec(chain_id.clone());
+        proposer.verify_signature(verifier, &sign_bytes, signature)
+    }
+
+    /// Verify that the proposal was signed by the expected proposer of the
+    /// given validator set at the round of the proposal.
+    ///
+    /// The validator set must be the one of the proposal height, in its
+    /// state at round 0, as in [`validator::Set::proposer_at_round`].
+    pub fn verify_proposer<V>(
+        &self,
+        verifier: &V,
+        chain_id: &ChainId,
+        validator_set: &validator::Set,
+    ) -> Result<(), Error>
+    where
+        V: Verifier,
+    {
+        let proposer = validator_set
+            .proposer_at_round(self.round)
+            .ok_or_else(|| Error::invalid_proposal("empty validator set".into()))?;
+        self.verify(verifier, chain_id, &proposer)
+    }
+
     /// Consensus state from this proposal - This doesn't seem to be used anywhere.
     #[deprecated(
         since = "0.17.0",
@@ -299,6 +379,84 @@ mod tests {
         assert_eq!(got, want)
     }
 
+    #[cfg(feature = "rust-crypto")]
+    mod verify {
+        use super::*;
+        use crate::{
+            crypto::default::signature::Verifier,
+            test::{Fixture, CHAIN_ID},
+            validator,
+        };
+
+        fn validator_set(fixture: &Fixture) -> validator::Set {
+            // Select the proposer of round 0, as CometBFT does for new sets.
+            let mut validator_set = fixture.validator_set.clone();
+            validator_set.increment_proposer_priority(1);
+            validator_set
+        }
+
+        fn proposal(round: u16) -> Proposal {
+            Proposal {
+                msg_type: Type::Proposal,
+                height: Height::from(10_u32),
+                round: Round::from(round),
+                pol_round: None,
+                block_id: Some(BlockId {
+                    hash: Hash::Sha256([1; 32]),
+                    part_set_header: Header::new(1, Hash::Sha256([2; 32])).unwrap(),
+                }),
+                timestamp: Some(datetime!(2018-02-11 07:09:22.765 UTC).try_into().unwrap()),
+                signature: None,
+            }
+        }
+
+        #[test]
+        fn validate_basic() {
+            let chain_id = ChainId::from_str(CHAIN_ID).unwrap();
+            let fixture = Fixture::new(3);
+
+            let mut proposal = proposal(1);
+            assert!(proposal.validate_basic().is_err());
+            proposal.sign(chain_id, &fixture.keys[0]).unwrap();
+            proposal.validate_basic().unwrap();
+
+            let mut invalid = proposal.clone();
+            invalid.pol_round = Some(Round::from(1_u16));
+            assert!(invalid.validate_basic().is_err());
+
+            let mut invalid = proposal;
+            invalid.block_id = None;
+            assert!(invalid.validate_basic().is_err());
+        }
+
+        #[test]
+        fn verify_proposer() {
+            let chain_id = ChainId::from_str(CHAIN_ID).unwrap();
+            let fixture = Fixture::new(3);
+            let validator_set = validator_set(&fixture);
+
+            let first = validator_set.proposer_at_round(Round::from(0_u16)).unwrap();
+            let second = validator_set.proposer_at_round(Round::from(1_u16)).unwrap();
+            assert_ne!(first.address, second.address);
+
+            let mut proposal = proposal(1);
+            proposal
+                .sign(chain_id.clone(), fixture.key_of(&second))
+                .unwrap();
+            proposal.verify(&Verifier, &chain_id, &second).unwrap();
+            proposal
+                .verify_proposer(&Verifier, &chain_id, &validator_set)
+                .unwrap();
+            assert!(proposal.verify(&Verifier, &chain_id, &first).is_err());
+
+            let mut proposal = proposal.clone();
+            proposal.round = Round::from(0_u16);
+            assert!(proposal
+                .verify_proposer(&Verifier, &chain_id, &validator_set)
+                .is_err());
+        }
+    }
+
     tendermint_pb_modules! {
         use super::*;
 
@@ -14,7 +14,11 @@ use serde::{de, ser, Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use subtle_encoding::{base64, bech32, hex};
 
+#[cfg(feature = "bls12-381")]
+pub use crate::crypto::bls12_381::VerificationKey as Bls12_381;
 pub use crate::crypto::ed25519::VerificationKey as Ed25519;
+#[cfg(feature = "sr25519")]
+pub use crate::crypto::sr25519::VerificationKey as Sr25519;
 use crate::{error::Error, prelude::*};
 
 // Note:On the golang side this is generic in the sense that it could everything that implements
@@ -49,6 +53,26 @@ pub enum PublicKey {
         deserialize_with = "deserialize_secp256k1_base64"
     )]
     Secp256k1(Secp256k1),
+
+    /// BLS12-381 keys
+    #[cfg(feature = "bls12-381")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bls12-381")))]
+    #[serde(
+        rename = "cometbft/PubKeyBls12_381",
+        serialize_with = "serialize_bls12_381_base64",
+        deserialize_with = "deserialize_bls12_381_base64"
+    )]
+    Bls12_381(Bls12_381),
+
+    /// Sr25519 keys
+    #[cfg(feature = "sr25519")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sr25519")))]
+    #[serde(
+        rename = "tendermint/PubKeySr25519",
+        serialize_with = "serialize_sr25519_base64",
+        deserialize_with = "deserialize_sr25519_base64"
+    )]
+    Sr25519(Sr25519),
 }
 
 // Internal thunk type to facilitate deserialization from the raw Protobuf data
@@ -137,25 +161,34 @@ tendermint_pb_modules! {
                 return Self::from_raw_secp256k1(b)
                     .ok_or_else(|| Error::invalid_key("malformed key".to_string()));
             }
+            // BLS12-381 and Sr25519 keys share a field, and are told apart
+            // by their length.
+            if let Sum::Bls12381(_b) = sum {
+                #[cfg(feature = "bls12-381")]
+                if let Some(key) = Self::from_raw_bls12_381(_b) {
+                    return Ok(key);
+                }
+                #[cfg(feature = "sr25519")]
+                if let Some(key) = Self::from_raw_sr25519(_b) {
+                    return Ok(key);
+                }
+            }
             Err(Error::invalid_key("not an ed25519 key".to_string()))
         }
     }
 
     impl From<PublicKey> for RawPublicKey {
         fn from(value: PublicKey) -> Self {
-            match value {
-                PublicKey::Ed25519(ref pk) => RawPublicKey {
-                    sum: Some(Sum::Ed25519(
-                        pk.as_bytes().to_vec(),
-                    )),
-                },
+            let sum = match value {
+                PublicKey::Ed25519(ref pk) => Sum::Ed25519(pk.as_bytes().to_vec()),
                 #[cfg(feature = "secp256k1")]
-                PublicKey::Secp256k1(ref pk) => RawPublicKey {
-                    sum: Some(Sum::Secp256k1(
-                        pk.to_sec1_bytes().into(),
-                    )),
-                },
-            }
+                PublicKey::Secp256k1(ref pk) => Sum::Secp256k1(pk.to_sec1_bytes().into()),
+                #[cfg(feature = "bls12-381")]
+                PublicKey::Bls12_381(ref pk) => Sum::Bls12381(pk.as_bytes().to_vec()),
+                #[cfg(feature = "sr25519")]
+                PublicKey::Sr25519(ref pk) => Sum::Bls12381(pk.as_bytes().to_vec()),
+            };
+            RawPublicKey { sum: Some(sum) }
         }
     }
 }
@@ -175,6 +208,20 @@ impl PublicKey {
         Ed25519::try_from(bytes).map(PublicKey::Ed25519).ok()
     }
 
+    /// From raw BLS12-381 public key bytes, in compressed form
+    #[cfg(feature = "bls12-381")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bls12-381")))]
+    pub fn from_raw_bls12_381(bytes: &[u8]) -> Option<PublicKey> {
+        Bls12_381::try_from(bytes).map(PublicKey::Bls12_381).ok()
+    }
+
+    /// From raw Sr25519 public key bytes
+    #[cfg(feature = "sr25519")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sr25519")))]
+    pub fn from_raw_sr25519(bytes: &[u8]) -> Option<PublicKey> {
+        Sr25519::try_from(bytes).map(PublicKey::Sr25519).ok()
+    }
+
     /// From an [`ed25519_consensus::VerificationKey`]
     #[cfg(feature = "rust-crypto")]
     pub fn from_ed25519_consensus(vk: ed25519_consensus::VerificationKey) -> Self {
@@ -200,12 +247,49 @@ impl PublicKey {
         }
     }
 
+    /// Get BLS12-381 public key
+    #[cfg(feature = "bls12-381")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bls12-381")))]
+    pub fn bls12_381(self) -> Option<Bls12_381> {
+        match self {
+            PublicKey::Bls12_381(pk) => Some(pk),
+            _ => None,
+        }
+    }
+
+    /// Get Sr25519 public key
+    #[cfg(feature = "sr25519")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sr25519")))]
+    pub fn sr25519(self) -> Option<Sr25519> {
+        match self {
+            PublicKey::Sr25519(pk) => Some(pk),
+            _ => None,
+        }
+    }
+
+    /// Get the algorithm of this key
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            PublicKey::Ed25519(_) => Algorithm::Ed25519,
+            #[cfg(feature = "secp256k1")]
+            PublicKey::Secp256k1(_) => Algorithm::Secp256k1,
+            #[cfg(feature = "bls12-381")]
+            PublicKey::Bls12_381(_) => Algorithm::Bls12_381,
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => Algorithm::Sr25519,
+        }
+    }
+
     /// Serialize this key as a byte vector.
     pub fn to_bytes(self) -> Vec<u8> {
         match self {
             PublicKey::Ed25519(pk) => pk.as_bytes().to_vec(),
             #[cfg(feature = "secp256k1")]
             PublicKey::Secp256k1(pk) => pk.to_sec1_bytes().into(),
+            #[cfg(feature = "bls12-381")]
+            PublicKey::Bls12_381(pk) => pk.as_bytes().to_vec(),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(pk) => pk.as_bytes().to_vec(),
         }
     }
 
@@ -223,6 +307,18 @@ impl PublicKey {
                 key_bytes.extend(pk.to_sec1_bytes().as_ref());
                 key_bytes
             },
+            #[cfg(feature = "bls12-381")]
+            PublicKey::Bls12_381(ref pk) => {
+                let mut key_bytes = vec![0x6C, 0x33, 0x80, 0x95, 0x30];
+                key_bytes.extend(pk.as_bytes());
+                key_bytes
+            },
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(ref pk) => {
+                let mut key_bytes = vec![0x0D, 0xFB, 0x10, 0x05, 0x20];
+                key_bytes.extend(pk.as_bytes());
+                key_bytes
+            },
         };
         bech32::encode(hrp, backward_compatible_amino_prefixed_pubkey)
     }
@@ -246,6 +342,20 @@ impl From<Secp256k1> for PublicKey {
     }
 }
 
+#[cfg(feature = "bls12-381")]
+impl From<Bls12_381> for PublicKey {
+    fn from(pk: Bls12_381) -> PublicKey {
+        PublicKey::Bls12_381(pk)
+    }
+}
+
+#[cfg(feature = "sr25519")]
+impl From<Sr25519> for PublicKey {
+    fn from(pk: Sr25519) -> PublicKey {
+        PublicKey::Sr25519(pk)
+    }
+}
+
 #[cfg(feature = "rust-crypto")]
 impl From<ed25519_consensus::VerificationKey> for PublicKey {
     fn from(vk: ed25519_consensus::VerificationKey) -> PublicKey {
@@ -261,18 +371,17 @@ impl PartialOrd for PublicKey {
 
 impl Ord for PublicKey {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self {
-            PublicKey::Ed25519(a) => match other {
-                PublicKey::Ed25519(b) => a.as_bytes().cmp(b.as_bytes()),
-                #[cfg(feature = "secp256k1")]
-                PublicKey::Secp256k1(_) => Ordering::Less,
-            },
+        #[allow(unreachable_patterns)]
+        match (self, other) {
+            (PublicKey::Ed25519(a), PublicKey::Ed25519(b)) => a.as_bytes().cmp(b.as_bytes()),
             #[cfg(feature = "secp256k1")]
-            PublicKey::Secp256k1(a) => match other {
-                PublicKey::Ed25519(_) => Ordering::Greater,
-                #[cfg(feature = "secp256k1")]
-                PublicKey::Secp256k1(b) => a.cmp(b),
-            },
+            (PublicKey::Secp256k1(a), PublicKey::Secp256k1(b)) => a.cmp(b),
+            #[cfg(feature = "bls12-381")]
+            (PublicKey::Bls12_381(a), PublicKey::Bls12_381(b)) => a.as_bytes().cmp(b.as_bytes()),
+            #[cfg(feature = "sr25519")]
+            (PublicKey::Sr25519(a), PublicKey::Sr25519(b)) => a.as_bytes().cmp(b.as_bytes()),
+            // Keys of different types are ordered by algorithm
+            _ => self.algorithm().cmp(&other.algorithm()),
         }
     }
 }
@@ -294,6 +403,10 @@ impl TendermintKey {
             PublicKey::Ed25519(_) => Ok(TendermintKey::AccountKey(public_key)),
             #[cfg(feature = "secp256k1")]
             PublicKey::Secp256k1(_) => Ok(TendermintKey::AccountKey(public_key)),
+            #[cfg(feature = "bls12-381")]
+            PublicKey::Bls12_381(_) => Ok(TendermintKey::AccountKey(public_key)),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => Ok(TendermintKey::AccountKey(public_key)),
         }
     }
 
@@ -304,9 +417,14 @@ impl TendermintKey {
             PublicKey::Ed25519(_) => Ok(TendermintKey::AccountKey(public_key)),
             #[cfg(feature = "secp256k1")]
             PublicKey::Secp256k1(_) => Ok(TendermintKey::AccountKey(public_key)),
+            #[cfg(feature = "bls12-381")]
+            PublicKey::Bls12_381(_) => Ok(TendermintKey::AccountKey(public_key)),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => Ok(TendermintKey::AccountKey(public_key)),
 
             _ => Err(Error::invalid_key(
-                "only ed25519 or secp256k1 consensus keys are supported".to_string(),
+                "only ed25519, secp256k1, bls12_381 or sr25519 consensus keys are supported"
+                    .to_string(),
             )),
         }
     }
@@ -321,13 +439,19 @@ impl TendermintKey {
 }
 
 /// Public key algorithms
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub enum Algorithm {
     /// ed25519
     Ed25519,
 
     /// secp256k1
     Secp256k1,
+
+    /// bls12_381
+    Bls12_381,
+
+    /// sr25519
+    Sr25519,
 }
 
 impl Algorithm {
@@ -336,6 +460,8 @@ impl Algorithm {
         match self {
             Algorithm::Ed25519 => "ed25519",
             Algorithm::Secp256k1 => "secp256k1",
+            Algorithm::Bls12_381 => "bls12_381",
+            Algorithm::Sr25519 => "sr25519",
         }
     }
 }
@@ -353,11 +479,31 @@ impl FromStr for Algorithm {
         match s {
             "ed25519" => Ok(Algorithm::Ed25519),
             "secp256k1" => Ok(Algorithm::Secp256k1),
+            "bls12_381" => Ok(Algorithm::Bls12_381),
+            "sr25519" => Ok(Algorithm::Sr25519),
             _ => Err(Error::parse(format!("invalid algorithm: {s}"))),
         }
     }
 }
 
+/// Parses the key type names used in `ValidatorParams.pub_key_types`.
+///
+/// Unlike [`FromStr`], this also accepts the capitalized names, and rejects
+/// anything else with [`Error::unsupported_key_type`], as CometBFT does.
+impl TryFrom<&str> for Algorithm {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Error> {
+        match s {
+            "ed25519" | "Ed25519" => Ok(Algorithm::Ed25519),
+            "secp256k1" | "Secp256k1" => Ok(Algorithm::Secp256k1),
+            "bls12_381" | "Bls12_381" => Ok(Algorithm::Bls12_381),
+            "sr25519" | "Sr25519" => Ok(Algorithm::Sr25519),
+            _ => Err(Error::unsupported_key_type()),
+        }
+    }
+}
+
 impl Serialize for Algorithm {
     fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         self.as_str().serialize(serializer)
@@ -414,6 +560,50 @@ where
     Secp256k1::from_sec1_bytes(&bytes).map_err(|_| D::Error::custom("invalid secp256k1 key"))
 }
 
+/// Serialize the bytes of a BLS12-381 public key as Base64. Used for serializing JSON
+#[cfg(feature = "bls12-381")]
+fn serialize_bls12_381_base64<S>(pk: &Bls12_381, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    String::from_utf8(base64::encode(pk.as_bytes()))
+        .unwrap()
+        .serialize(serializer)
+}
+
+#[cfg(feature = "bls12-381")]
+fn deserialize_bls12_381_base64<'de, D>(deserializer: D) -> Result<Bls12_381, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use de::Error;
+    let encoded = String::deserialize(deserializer)?;
+    let bytes = base64::decode(encoded).map_err(D::Error::custom)?;
+    Bls12_381::try_from(&bytes[..]).map_err(|_| D::Error::custom("invalid BLS12-381 key"))
+}
+
+/// Serialize the bytes of an Sr25519 public key as Base64. Used for serializing JSON
+#[cfg(feature = "sr25519")]
+fn serialize_sr25519_base64<S>(pk: &Sr25519, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    String::from_utf8(base64::encode(pk.as_bytes()))
+        .unwrap()
+        .serialize(serializer)
+}
+
+#[cfg(feature = "sr25519")]
+fn deserialize_sr25519_base64<'de, D>(deserializer: D) -> Result<Sr25519, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use de::Error;
+    let encoded = String::deserialize(deserializer)?;
+    let bytes = base64::decode(encoded).map_err(D::Error::custom)?;
+    Sr25519::try_from(&bytes[..]).map_err(|_| D::Error::custom("invalid Sr25519 key"))
+}
+
 #[cfg(test)]
 mod tests {
     use subtle_encoding::hex;
@@ -483,6 +673,32 @@ mod tests {
         assert_eq!(reserialized_json.as_str(), json_string);
     }
 
+    #[test]
+    #[cfg(feature = "bls12-381")]
+    fn bls12_381_json_parsing() {
+        let json_string = "{\"type\":\"cometbft/PubKeyBls12_381\",\"value\":\"pQqnhnZYp6NWnkNTKdKyoWJWNZBfVyB2d/B5rNAlVZq8g8OGO6ovuS0ITsbz4JXs\"}";
+        let pubkey: PublicKey = serde_json::from_str(json_string).unwrap();
+
+        assert_eq!(pubkey.bls12_381().unwrap().as_bytes().len(), 48);
+        assert_eq!(pubkey.algorithm().as_str(), "bls12_381");
+
+        let reserialized_json = serde_json::to_string(&pubkey).unwrap();
+        assert_eq!(reserialized_json.as_str(), json_string);
+    }
+
+    #[test]
+    #[cfg(feature = "sr25519")]
+    fn sr25519_json_parsing() {
+        let json_string = "{\"type\":\"tendermint/PubKeySr25519\",\"value\":\"RblzMO4is5L1hZz6wo4kPbptzOyue6LTk4+lPhD1FRk=\"}";
+        let pubkey: PublicKey = serde_json::from_str(json_string).unwrap();
+
+        assert_eq!(pubkey.sr25519().unwrap().as_bytes().len(), 32);
+        assert_eq!(pubkey.algorithm().as_str(), "sr25519");
+
+        let reserialized_json = serde_json::to_string(&pubkey).unwrap();
+        assert_eq!(reserialized_json.as_str(), json_string);
+    }
+
     tendermint_pb_modules! {
         use super::*;
         use pb::privval::PubKeyResponse as RawPubKeyResponse;
@@ -535,5 +751,50 @@ mod tests {
             ).unwrap();
             assert_eq!(decoded, msg);
         }
+
+        fn assert_protobuf_round_trip(pk: PublicKey) {
+            let encoded = Protobuf::<pb::crypto::PublicKey>::encode_vec(pk);
+            let decoded = <PublicKey as Protobuf<pb::crypto::PublicKey>>::decode_vec(
+                &encoded
+            ).unwrap();
+            assert_eq!(decoded, pk);
+        }
+
+        #[test]
+        fn ed25519_protobuf_round_trip() {
+            assert_protobuf_round_trip(
+                PublicKey::from_raw_ed25519(&hex::decode_upper(EXAMPLE_CONSENSUS_KEY).unwrap())
+                    .unwrap(),
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "secp256k1")]
+        fn secp256k1_protobuf_round_trip() {
+            assert_protobuf_round_trip(
+                PublicKey::from_raw_secp256k1(&hex::decode_upper(
+                    "02A1633CAFCC01EBFB6D78E39F687A1F0995C62FC95F51EAD10A02EE0BE551B5DC",
+                ).unwrap()).unwrap(),
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "bls12-381")]
+        fn bls12_381_protobuf_round_trip() {
+            assert_protobuf_round_trip(
+                PublicKey::from_raw_bls12_381(&hex::decode(
+                    "a50aa7867658a7a3569e435329d2b2a1625635905f57207677f079acd025559abc83c3863baa2fb92d084ec6f3e095ec",
+                ).unwrap()).unwrap(),
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "sr25519")]
+        fn sr25519_protobuf_round_trip() {
+            assert_protobuf_round_trip(
+                PublicKey::from_raw_sr25519(&hex::decode_upper(EXAMPLE_CONSENSUS_KEY).unwrap())
+                    .unwrap(),
+            );
+        }
     }
 }
@@ -1,4 +1,4 @@
-use serde::{Deserialize, Deserializer};
+use serde::{ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Deserialize `null` or an empty object `{}` as `None`.
 pub fn allow_empty_object<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
@@ -24,3 +24,18 @@ where
         Err(e) => Err(e),
     }
 }
+
+/// Serialize `None` as an empty object `{}`, as Go does for an empty struct.
+pub fn serialize_none_as_empty_object<S, T>(
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    match value {
+        Some(value) => value.serialize(serializer),
+        None => serializer.serialize_map(Some(0))?.end(),
+    }
+}
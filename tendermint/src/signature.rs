@@ -10,9 +10,13 @@ use tendermint_proto::Protobuf;
 
 use crate::{error::Error, prelude::*};
 
-/// The expected length of all currently supported signatures, in bytes.
+/// The expected length of Ed25519, Secp256k1 and Sr25519 signatures, in bytes.
 pub const SIGNATURE_LENGTH: usize = 64;
 
+/// The expected length of BLS12-381 signatures, in bytes.
+#[cfg(feature = "bls12-381")]
+pub const BLS12_381_SIGNATURE_LENGTH: usize = crate::crypto::bls12_381::SIGNATURE_LENGTH;
+
 /// Signatures
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Signature(Vec<u8>);
@@ -64,6 +68,10 @@ impl Signature {
         if bytes.is_empty() {
             return Ok(None);
         }
+        #[cfg(feature = "bls12-381")]
+        if bytes.len() == BLS12_381_SIGNATURE_LENGTH {
+            return Ok(Some(Self(bytes.to_vec())));
+        }
         if bytes.len() != SIGNATURE_LENGTH {
             return Err(Error::signature_invalid(format!(
                 "expected signature to be {} bytes long, but was {} bytes",
@@ -35,3 +35,100 @@ pub fn dummy_signature() -> Signature {
         &[0; Ed25519Signature::BYTE_SIZE],
     ))
 }
+
+#[cfg(feature = "rust-crypto")]
+pub use self::fixture::*;
+
+#[cfg(feature = "rust-crypto")]
+mod fixture {
+    use ed25519_consensus::SigningKey;
+
+    use crate::{
+        block::{self, Height},
+        prelude::*,
+        validator,
+        vote::{self, Vote},
+        PublicKey, Time,
+    };
+
+    /// Chain ID that test votes are signed for.
+    pub const CHAIN_ID: &str = "test-chain";
+
+    /// Deterministic Ed25519 signing keys.
+    pub fn signing_keys(n: u8) -> Vec<SigningKey> {
+        (0..n).map(|i| SigningKey::from([i + 1; 32])).collect()
+    }
+
+    /// The public key of a signing key.
+    pub fn public_key(key: &SigningKey) -> PublicKey {
+        PublicKey::from_raw_ed25519(key.verification_key().as_bytes()).unwrap()
+    }
+
+    /// A validator set with a voting power of 10 for each signing key, and
+    /// the signing keys in the order of the set.
+    pub struct Fixture {
+        pub keys: Vec<SigningKey>,
+        pub validator_set: validator::Set,
+    }
+
+    impl Fixture {
+        /// A fixture with `n` validators, see [`signing_keys`].
+        pub fn new(n: u8) -> Self {
+            Self::from_keys(signing_keys(n))
+        }
+
+        /// A fixture with a validator for each of the given keys.
+        pub fn from_keys(mut keys: Vec<SigningKey>) -> Self {
+            let validators = keys
+                .iter()
+                .map(|key| validator::Info::new(public_key(key), 10u32.into()))
+                .collect();
+            let validator_set = validator::Set::without_proposer(validators);
+            // Order the keys as the validators.
+            keys.sort_by_key(|key| {
+                validator_set
+                    .validators()
+                    .iter()
+                    .position(|v| v.pub_key == public_key(key))
+            });
+            Self {
+                keys,
+                validator_set,
+            }
+        }
+
+        /// The signing key of a validator of the set.
+        pub fn key_of(&self, validator: &validator::Info) -> &SigningKey {
+            self.keys
+                .iter()
+                .find(|key| public_key(key) == validator.pub_key)
+                .unwrap()
+        }
+
+        /// A round 0 vote of the validator at `index`, signed for
+        /// [`CHAIN_ID`].
+        pub fn vote(
+            &self,
+            index: usize,
+            vote_type: vote::Type,
+            height: Height,
+            block_id: Option<block::Id>,
+        ) -> Vote {
+            let mut vote = Vote {
+                vote_type,
+                height,
+                round: 0u16.into(),
+                block_id,
+                timestamp: Some(Time::unix_epoch()),
+                validator_address: self.validator_set.validators()[index].address,
+                validator_index: index.try_into().unwrap(),
+                signature: None,
+                extension: vec![],
+                extension_signature: None,
+            };
+            vote.sign(CHAIN_ID.parse().unwrap(), &self.keys[index])
+                .unwrap();
+            vote
+        }
+    }
+}
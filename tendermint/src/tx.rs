@@ -1,3 +1,39 @@
+//! Transactions
+
 mod proof;
 
 pub use proof::Proof;
+
+use crate::{
+    crypto::Sha256,
+    merkle::{self, MerkleHash},
+    prelude::*,
+    Hash,
+};
+
+/// Compute the hash of a transaction.
+#[cfg(feature = "rust-crypto")]
+pub fn hash(tx: &[u8]) -> Hash {
+    hash_with::<crate::crypto::default::Sha256>(tx)
+}
+
+/// Hash a transaction with a SHA-256 hasher provided by a crypto provider.
+pub fn hash_with<H: Sha256>(tx: &[u8]) -> Hash {
+    Hash::Sha256(H::digest(tx))
+}
+
+/// Compute the Merkle root of the hashes of the given transactions, as
+/// committed to by the `data_hash` field of a block header.
+#[cfg(feature = "rust-crypto")]
+pub fn data_hash(txs: &[impl AsRef<[u8]>]) -> Hash {
+    data_hash_with::<crate::crypto::default::Sha256>(txs)
+}
+
+/// Compute the data hash with a Merkle hasher provided by a crypto provider.
+pub fn data_hash_with<H>(txs: &[impl AsRef<[u8]>]) -> Hash
+where
+    H: MerkleHash + Sha256 + Default,
+{
+    let tx_hashes = txs.iter().map(|tx| H::digest(tx)).collect::<Vec<_>>();
+    Hash::Sha256(merkle::simple_hash_from_byte_vectors::<H>(&tx_hashes))
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use tendermint_proto::v0_37::types::TxProof as RawTxProof;
 use tendermint_proto::Protobuf;
 
-use crate::{merkle, prelude::*, Error, Hash};
+use crate::{crypto::Sha256, merkle, merkle::MerkleHash, prelude::*, Error, Hash};
 
 /// Merkle proof of the presence of a transaction in the Merkle tree.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -13,6 +13,35 @@ pub struct Proof {
     pub proof: merkle::Proof,
 }
 
+impl Proof {
+    /// Check that this proof attests to the inclusion of the transaction in
+    /// a block with the given data hash.
+    #[cfg(feature = "rust-crypto")]
+    pub fn validate(&self, data_hash: &Hash) -> Result<(), Error> {
+        self.validate_with::<crate::crypto::default::Sha256>(data_hash)
+    }
+
+    /// Check this proof with a Merkle hasher provided by a crypto provider.
+    pub fn validate_with<H>(&self, data_hash: &Hash) -> Result<(), Error>
+    where
+        H: MerkleHash + Sha256 + Default,
+    {
+        if &self.root_hash != data_hash {
+            return Err(Error::invalid_merkle_proof(
+                "proof matches different data hash".to_string(),
+            ));
+        }
+        if self.proof.total == 0 {
+            return Err(Error::invalid_merkle_proof(
+                "proof total must be positive".to_string(),
+            ));
+        }
+        // The leaves of the data hash tree are the hashes of the transactions.
+        self.proof
+            .verify_with::<H>(&self.root_hash, &H::digest(&self.data))
+    }
+}
+
 impl Protobuf<RawTxProof> for Proof {}
 
 impl TryFrom<RawTxProof> for Proof {
@@ -36,3 +65,35 @@ impl From<Proof> for RawTxProof {
         }
     }
 }
+
+#[cfg(all(test, feature = "rust-crypto"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let txs = [b"tx1", b"tx2"];
+        let leaves = txs.map(crate::crypto::default::Sha256::digest);
+        let root = Hash::Sha256(merkle::simple_hash_from_byte_vectors::<sha2::Sha256>(
+            &leaves,
+        ));
+
+        let mut hasher = sha2::Sha256::default();
+        let proof = Proof {
+            root_hash: root,
+            data: txs[1].to_vec(),
+            proof: merkle::Proof {
+                total: 2,
+                index: 1,
+                leaf_hash: Hash::Sha256(hasher.leaf_hash(&leaves[1])),
+                aunts: vec![Hash::Sha256(hasher.leaf_hash(&leaves[0]))],
+            },
+        };
+        proof.validate(&root).unwrap();
+        assert!(proof.validate(&Hash::Sha256([0; 32])).is_err());
+
+        let mut proof = proof;
+        proof.data = txs[0].to_vec();
+        assert!(proof.validate(&root).is_err());
+    }
+}
@@ -1,24 +1,31 @@
 //! Tendermint validators
 
-use serde::{Deserialize, Serialize};
+use alloc::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize, Serializer};
 use tendermint_proto::v0_38::types::{
     SimpleValidator as RawSimpleValidator, ValidatorSet as RawValidatorSet,
 };
 use tendermint_proto::Protobuf;
 
 use crate::{
-    account,
+    account, block,
+    block::{Commit, CommitSig},
+    chain,
     crypto::signature::Verifier,
     crypto::Sha256,
     hash::Hash,
     merkle::{self, MerkleHash},
     prelude::*,
     public_key::deserialize_public_key,
-    vote, Error, PublicKey, Signature,
+    serializers,
+    trust_threshold::TrustThresholdFraction,
+    vote::{self, ValidatorIndex},
+    Error, PublicKey, Signature,
 };
 
 /// Validator set contains a vector of validators
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
 #[serde(try_from = "RawValidatorSet")]
 pub struct Set {
     /// Validators
@@ -29,9 +36,52 @@ pub struct Set {
     pub total_voting_power: vote::Power,
 }
 
+// Serialized as CometBFT does, with the validators in the form they take in a
+// validator set rather than in a genesis file, and without the total voting
+// power, which is not part of the JSON encoding in CometBFT.
+impl Serialize for Set {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Validator<'a> {
+            address: account::Id,
+            pub_key: &'a PublicKey,
+            voting_power: vote::Power,
+            #[serde(with = "serializers::from_str")]
+            proposer_priority: i64,
+        }
+
+        impl<'a> From<&'a Info> for Validator<'a> {
+            fn from(info: &'a Info) -> Self {
+                Validator {
+                    address: info.address,
+                    pub_key: &info.pub_key,
+                    voting_power: info.power,
+                    proposer_priority: info.proposer_priority.value(),
+                }
+            }
+        }
+
+        #[derive(Serialize)]
+        struct ValidatorSet<'a> {
+            validators: Vec<Validator<'a>>,
+            proposer: Option<Validator<'a>>,
+        }
+
+        ValidatorSet {
+            validators: self.validators.iter().map(Into::into).collect(),
+            proposer: self.proposer.as_ref().map(Into::into),
+        }
+        .serialize(serializer)
+    }
+}
+
 impl Set {
     pub const MAX_TOTAL_VOTING_POWER: u64 = (i64::MAX / 8) as u64;
 
+    /// The maximum allowed difference between the proposer priorities of the
+    /// validators, as a multiple of the total voting power.
+    pub const PRIORITY_WINDOW_SIZE_FACTOR: i64 = 2;
+
     /// Constructor
     pub fn new(validators: Vec<Info>, proposer: Option<Info>) -> Set {
         Self::try_from_parts(validators, proposer, 0).unwrap()
@@ -143,6 +193,449 @@ impl Set {
 
         Hash::Sha256(merkle::simple_hash_from_byte_vectors::<H>(&validator_bytes))
     }
+
+    /// Advance the proposer priorities `times` rounds, and set the proposer
+    /// to the validator selected in the last round.
+    ///
+    /// The priorities are first rescaled to fit within the priority window
+    /// and centered around zero, as done by CometBFT.
+    pub fn increment_proposer_priority(&mut self, times: u32) {
+        if self.validators.is_empty() {
+            return;
+        }
+
+        let diff_max = Self::PRIORITY_WINDOW_SIZE_FACTOR * self.total_power_i64();
+        self.rescale_priorities(diff_max);
+        self.shift_by_avg_proposer_priority();
+
+        for _ in 0..times {
+            let proposer = self.increment_proposer_priority_once();
+            self.proposer = Some(proposer);
+        }
+    }
+
+    /// Apply a set of changes to the validators: validators with a voting
+    /// power of zero are removed, while the others are added or have their
+    /// voting power updated.
+    ///
+    /// New validators start with a proposer priority of `-1.125` times the
+    /// total voting power, so that a validator cannot reset a negative
+    /// priority by unbonding and bonding again. The priorities are then
+    /// rescaled and centered. The proposer is left unchanged.
+    ///
+    /// The set is left untouched if the changes are invalid: if they contain
+    /// duplicate addresses, remove unknown validators, or would leave the set
+    /// empty or exceeding [`Self::MAX_TOTAL_VOTING_POWER`].
+    pub fn update_with_change_set(&mut self, changes: &[Info]) -> Result<(), Error> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let mut changes = changes.to_vec();
+        changes.sort_by_key(|v| v.address);
+        if let Some(w) = changes.windows(2).find(|w| w[0].address == w[1].address) {
+            return Err(Error::invalid_validator_update(format!(
+                "duplicate entry for validator {}",
+                w[0].address
+            )));
+        }
+        if let Some(v) = changes
+            .iter()
+            .find(|v| v.power() > Self::MAX_TOTAL_VOTING_POWER)
+        {
+            return Err(Error::invalid_validator_update(format!(
+                "voting power of validator {} can't be higher than {}, got {}",
+                v.address,
+                Self::MAX_TOTAL_VOTING_POWER,
+                v.power
+            )));
+        }
+        let (removals, mut updates): (Vec<_>, Vec<_>) =
+            changes.into_iter().partition(|v| v.power() == 0);
+
+        let num_new_validators = updates
+            .iter()
+            .filter(|v| self.validator(v.address).is_none())
+            .count();
+        if num_new_validators == 0 && self.validators.len() == removals.len() {
+            return Err(Error::invalid_validator_update(
+                "applying the validator changes would result in empty set".to_string(),
+            ));
+        }
+
+        let mut removed_power = 0u64;
+        for removal in &removals {
+            let val = self.validator(removal.address).ok_or_else(|| {
+                Error::invalid_validator_update(format!(
+                    "failed to find validator {} to remove",
+                    removal.address
+                ))
+            })?;
+            removed_power += val.power();
+        }
+
+        let total_power_after_updates = self.verify_updates(&updates, removed_power)?;
+
+        // Compute the priorities of the updated validators.
+        for update in &mut updates {
+            update.proposer_priority = match self.validator(update.address) {
+                Some(val) => val.proposer_priority,
+                None => {
+                    let power = total_power_after_updates as i64;
+                    ProposerPriority(-(power + (power >> 3)))
+                },
+            };
+        }
+
+        // Apply the updates and removals.
+        let mut validators = core::mem::take(&mut self.validators)
+            .into_iter()
+            .filter(|v| {
+                !removals.iter().any(|r| r.address == v.address)
+                    && !updates.iter().any(|u| u.address == v.address)
+            })
+            .collect::<Vec<_>>();
+        validators.extend(updates);
+        self.validators = validators;
+        self.total_voting_power = self
+            .validators
+            .iter()
+            .map(|v| v.power())
+            .sum::<u64>()
+            .try_into()
+            .expect("total voting power was checked against the maximum");
+
+        // Scale and center.
+        let diff_max = Self::PRIORITY_WINDOW_SIZE_FACTOR * self.total_power_i64();
+        self.rescale_priorities(diff_max);
+        self.shift_by_avg_proposer_priority();
+
+        Self::sort_validators(&mut self.validators);
+        Ok(())
+    }
+
+    /// Apply the validator updates returned by the application at the end of
+    /// a block, then advance the proposer priorities by one round.
+    ///
+    /// Applied to the next validator set of a block, this yields the next
+    /// validator set of the following block, as computed by CometBFT.
+    #[cfg(feature = "rust-crypto")]
+    pub fn apply_updates(&mut self, updates: &[Update]) -> Result<(), Error> {
+        let changes = updates
+            .iter()
+            .map(|update| Info::new(update.pub_key, update.power))
+            .collect::<Vec<_>>();
+        self.update_with_change_set(&changes)?;
+        self.increment_proposer_priority(1);
+        Ok(())
+    }
+
+    /// The total voting power, which always fits in an `i64`.
+    fn total_power_i64(&self) -> i64 {
+        self.total_voting_power.value() as i64
+    }
+
+    /// Check that applying the updates after the removals will not exceed the
+    /// maximum total voting power at any point, and return the total voting
+    /// power after the updates but before the removals.
+    fn verify_updates(&self, updates: &[Info], removed_power: u64) -> Result<u64, Error> {
+        let delta = |update: &Info| -> i64 {
+            let current = self.validator(update.address).map_or(0, |v| v.power());
+            update.power() as i64 - current as i64
+        };
+        let mut deltas = updates.iter().map(delta).collect::<Vec<_>>();
+        // Apply the decreases first, as they are applied in CometBFT.
+        deltas.sort_unstable();
+
+        let mut total_power = self.total_power_i64() - removed_power as i64;
+        for delta in deltas {
+            total_power += delta;
+            if total_power > Self::MAX_TOTAL_VOTING_POWER as i64 {
+                return Err(Error::total_voting_power_overflow());
+            }
+        }
+        Ok((total_power + removed_power as i64) as u64)
+    }
+
+    /// Scale down the priorities so that the difference between the highest
+    /// and the lowest is at most `diff_max`.
+    fn rescale_priorities(&mut self, diff_max: i64) {
+        if diff_max <= 0 {
+            return;
+        }
+        let priorities = self.validators.iter().map(|v| v.proposer_priority.0);
+        let (Some(max), Some(min)) = (priorities.clone().max(), priorities.min()) else {
+            return;
+        };
+        let diff = max.saturating_sub(min);
+        if diff > diff_max {
+            let ratio = (diff + diff_max - 1) / diff_max;
+            for val in &mut self.validators {
+                val.proposer_priority.0 /= ratio;
+            }
+        }
+    }
+
+    /// Center the priorities around zero, by subtracting their average
+    /// (rounded towards negative infinity) from each of them.
+    fn shift_by_avg_proposer_priority(&mut self) {
+        let n = self.validators.len() as i128;
+        let sum = self
+            .validators
+            .iter()
+            .map(|v| i128::from(v.proposer_priority.0))
+            .sum::<i128>();
+        // The average of `i64` values always fits in an `i64`.
+        let avg = sum.div_euclid(n) as i64;
+        for val in &mut self.validators {
+            val.proposer_priority.0 = val.proposer_priority.0.saturating_sub(avg);
+        }
+    }
+
+    /// Run a single round of proposer selection, returning the selected
+    /// proposer.
+    fn increment_proposer_priority_once(&mut self) -> Info {
+        for val in &mut self.validators {
+            val.proposer_priority.0 = val.proposer_priority.0.saturating_add(val.power() as i64);
+        }
+        let total_power = self.total_power_i64();
+        let proposer = self
+            .validators
+            .iter_mut()
+            .max_by(|a, b| Self::cmp_proposer_priority(a, b))
+            .expect("validator set is not empty");
+        proposer.proposer_priority.0 = proposer.proposer_priority.0.saturating_sub(total_power);
+        proposer.clone()
+    }
+
+    /// Order validators by proposer priority, breaking ties in favor of the
+    /// lowest address.
+    fn cmp_proposer_priority(a: &Info, b: &Info) -> core::cmp::Ordering {
+        a.proposer_priority
+            .cmp(&b.proposer_priority)
+            .then_with(|| b.address.cmp(&a.address))
+    }
+
+    /// Get the expected proposer at the given round of the height for which
+    /// this validator set is the current one, assuming the set is in its
+    /// state at round 0.
+    ///
+    /// Returns `None` if the validator set is empty.
+    pub fn proposer_at_round(&self, round: block::Round) -> Option<Info> {
+        if round.value() == 0 {
+            return self.proposer.clone().or_else(|| {
+                self.validators
+                    .iter()
+                    .max_by(|a, b| Self::cmp_proposer_priority(a, b))
+                    .cloned()
+            });
+        }
+        let mut validators = self.clone();
+        validators.increment_proposer_priority(round.value());
+        validators.proposer
+    }
+}
+
+/// Verification of commits against a validator set.
+///
+/// Based on <https://github.com/cometbft/cometbft/blob/v0.38.x/types/validation.go>
+impl Set {
+    /// Verify that more than 2/3 of the voting power of this validator set
+    /// signed the given block at the given height.
+    ///
+    /// The signatures must be in the same order as the validators. Every
+    /// signature in the commit is checked, including votes for nil, which
+    /// do not count towards the voting power.
+    pub fn verify_commit<V>(
+        &self,
+        verifier: &V,
+        chain_id: &chain::Id,
+        block_id: &block::Id,
+        height: block::Height,
+        commit: &Commit,
+    ) -> Result<(), Error>
+    where
+        V: Verifier,
+    {
+        self.verify_basic_commit(block_id, height, commit)?;
+        let needed = self.power_needed(2, 3);
+        self.verify_commit_signatures(
+            verifier,
+            chain_id,
+            commit,
+            needed,
+            |sig| sig.is_absent(),
+            true,
+            true,
+        )
+    }
+
+    /// Verify that more than 2/3 of the voting power of this validator set
+    /// signed the given block at the given height.
+    ///
+    /// Unlike [`Set::verify_commit`], only votes for the block are checked,
+    /// and verification stops as soon as enough voting power is reached.
+    pub fn verify_commit_light<V>(
+        &self,
+        verifier: &V,
+        chain_id: &chain::Id,
+        block_id: &block::Id,
+        height: block::Height,
+        commit: &Commit,
+    ) -> Result<(), Error>
+    where
+        V: Verifier,
+    {
+        self.verify_basic_commit(block_id, height, commit)?;
+        let needed = self.power_needed(2, 3);
+        self.verify_commit_signatures(
+            verifier,
+            chain_id,
+            commit,
+            needed,
+            |sig| !sig.is_commit(),
+            false,
+            true,
+        )
+    }
+
+    /// Verify that more than `trust_threshold` of the voting power of this
+    /// validator set signed the given commit.
+    ///
+    /// This validator set does not need to be the one that produced the
+    /// commit: the signatures are matched to the validators by address, and
+    /// the signatures of unknown validators are ignored. Only votes for the
+    /// block are checked, and verification stops as soon as enough voting
+    /// power is reached.
+    pub fn verify_commit_light_trusting<V>(
+        &self,
+        verifier: &V,
+        chain_id: &chain::Id,
+        commit: &Commit,
+        trust_threshold: TrustThresholdFraction,
+    ) -> Result<(), Error>
+    where
+        V: Verifier,
+    {
+        let needed = self.power_needed(trust_threshold.numerator(), trust_threshold.denominator());
+        self.verify_commit_signatures(
+            verifier,
+            chain_id,
+            commit,
+            needed,
+            |sig| !sig.is_commit(),
+            false,
+            false,
+        )
+    }
+
+    /// Check that the commit is for the given block and height, and has one
+    /// signature per validator.
+    fn verify_basic_commit(
+        &self,
+        block_id: &block::Id,
+        height: block::Height,
+        commit: &Commit,
+    ) -> Result<(), Error> {
+        if self.validators.len() != commit.signatures.len() {
+            return Err(Error::invalid_commit(format!(
+                "invalid commit -- wrong set size: {} vs {}",
+                self.validators.len(),
+                commit.signatures.len()
+            )));
+        }
+        if height != commit.height {
+            return Err(Error::invalid_commit(format!(
+                "invalid commit -- wrong height: {} vs {}",
+                height, commit.height
+            )));
+        }
+        if block_id != &commit.block_id {
+            return Err(Error::invalid_commit(format!(
+                "invalid commit -- wrong block ID: want {}, got {}",
+                block_id, commit.block_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// The voting power that must be exceeded to reach the given fraction of
+    /// the total voting power.
+    fn power_needed(&self, numerator: u64, denominator: u64) -> u64 {
+        let total = u128::from(self.total_voting_power.value());
+        (total * u128::from(numerator) / u128::from(denominator)) as u64
+    }
+
+    /// Verify the signatures of a commit and tally the voting power of the
+    /// votes for the block.
+    ///
+    /// Signatures for which `ignore` returns `true` are skipped. If
+    /// `count_all` is `false`, verification stops as soon as the tallied
+    /// power exceeds `needed`. If `by_index` is `true`, the signatures are
+    /// matched to the validators by index, otherwise by address.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_commit_signatures<V>(
+        &self,
+        verifier: &V,
+        chain_id: &chain::Id,
+        commit: &Commit,
+        needed: u64,
+        ignore: fn(&CommitSig) -> bool,
+        count_all: bool,
+        by_index: bool,
+    ) -> Result<(), Error>
+    where
+        V: Verifier,
+    {
+        let mut tallied = 0u64;
+        let mut seen = BTreeMap::new();
+        for (index, commit_sig) in commit.signatures.iter().enumerate() {
+            if ignore(commit_sig) {
+                continue;
+            }
+
+            let validator = if by_index {
+                &self.validators[index]
+            } else {
+                let Some(address) = commit_sig.validator_address() else {
+                    continue;
+                };
+                let Some(position) = self.validators.iter().position(|v| v.address == address)
+                else {
+                    continue;
+                };
+                if let Some(first_index) = seen.insert(position, index) {
+                    return Err(Error::duplicate_validator_vote(address, first_index, index));
+                }
+                &self.validators[position]
+            };
+
+            let vote = ValidatorIndex::try_from(index)
+                .ok()
+                .and_then(|validator_index| commit.vote(validator_index))
+                .expect("vote exists for a non-absent signature");
+            let signature = vote
+                .signature
+                .clone()
+                .ok_or_else(|| Error::invalid_commit_signature(index, validator.address))?;
+            let sign_bytes = vote.into_signable_vec(chain_id.clone());
+            validator
+                .verify_signature(verifier, &sign_bytes, &signature)
+                .map_err(|_| Error::invalid_commit_signature(index, validator.address))?;
+
+            if commit_sig.is_commit() {
+                tallied += validator.power();
+            }
+            if !count_all && tallied > needed {
+                return Ok(());
+            }
+        }
+
+        if tallied <= needed {
+            return Err(Error::not_enough_voting_power_signed(tallied, needed));
+        }
+        Ok(())
+    }
 }
 
 /// Validator information
@@ -207,7 +700,6 @@ impl Info {
 /// It does not include the address, as that is redundant with the pubkey,
 /// nor the proposer priority, as that changes with every block even if the validator set didn't.
 /// It contains only the pubkey and the voting power.
-/// TODO: currently only works for Ed25519 pubkeys
 #[derive(Clone, PartialEq, Eq)]
 pub struct SimpleValidator {
     /// Public key
@@ -490,6 +982,441 @@ mod tests {
                 148_151_478_422_287_875 + 158_095_448_483_785_107 + 770_561_664_770_006_272
             );
         }
+
+        mod verify_commit {
+            use super::*;
+            use crate::{
+                block::parts::Header as PartSetHeader,
+                crypto::default::signature,
+                test::{signing_keys, Fixture, CHAIN_ID},
+            };
+
+            fn block_id() -> block::Id {
+                block::Id {
+                    hash: Hash::Sha256([1; 32]),
+                    part_set_header: PartSetHeader::new(1, Hash::Sha256([2; 32])).unwrap(),
+                }
+            }
+
+            /// A commit of the validators of the fixture, in the order of the set.
+            /// `None` stands for an absent vote, `Some(false)` for a vote for nil.
+            fn make_commit(fixture: &Fixture, votes: &[Option<bool>]) -> Commit {
+                let height = 3u32.into();
+                let signatures = votes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, for_block)| {
+                        let Some(for_block) = for_block else {
+                            return CommitSig::BlockIdFlagAbsent;
+                        };
+                        let vote = fixture.vote(
+                            index,
+                            vote::Type::Precommit,
+                            height,
+                            for_block.then(block_id),
+                        );
+                        let (validator_address, timestamp, signature) = (
+                            vote.validator_address,
+                            vote.timestamp.unwrap(),
+                            vote.signature,
+                        );
+                        if *for_block {
+                            CommitSig::BlockIdFlagCommit {
+                                validator_address,
+                                timestamp,
+                                signature,
+                            }
+                        } else {
+                            CommitSig::BlockIdFlagNil {
+                                validator_address,
+                                timestamp,
+                                signature,
+                            }
+                        }
+                    })
+                    .collect();
+                Commit {
+                    height,
+                    round: 0u16.into(),
+                    block_id: block_id(),
+                    signatures,
+                }
+            }
+
+            fn tamper(commit: &mut Commit, index: usize) {
+                match &mut commit.signatures[index] {
+                    CommitSig::BlockIdFlagCommit { signature, .. }
+                    | CommitSig::BlockIdFlagNil { signature, .. } => {
+                        let mut bytes = signature.as_ref().unwrap().as_bytes().to_vec();
+                        bytes[0] ^= 1;
+                        *signature = Signature::new(bytes).unwrap();
+                    },
+                    CommitSig::BlockIdFlagAbsent => unreachable!(),
+                }
+            }
+
+            #[test]
+            fn valid_commit() {
+                let fixture = Fixture::new(4);
+                let set = &fixture.validator_set;
+                let commit =
+                    make_commit(&fixture, &[Some(true), Some(true), Some(true), Some(false)]);
+                let chain_id = CHAIN_ID.parse().unwrap();
+                let verifier = signature::Verifier;
+
+                set.verify_commit(&verifier, &chain_id, &block_id(), commit.height, &commit)
+                    .unwrap();
+                set.verify_commit_light(&verifier, &chain_id, &block_id(), commit.height, &commit)
+                    .unwrap();
+                set.verify_commit_light_trusting(&verifier, &chain_id, &commit, Default::default())
+                    .unwrap();
+            }
+
+            #[test]
+            fn not_enough_voting_power() {
+                let fixture = Fixture::new(4);
+                let set = &fixture.validator_set;
+                let commit = make_commit(&fixture, &[Some(true), Some(true), Some(false), None]);
+                let chain_id = CHAIN_ID.parse().unwrap();
+                let verifier = signature::Verifier;
+
+                let err = set
+                    .verify_commit(&verifier, &chain_id, &block_id(), commit.height, &commit)
+                    .unwrap_err();
+                match err.detail() {
+                    crate::error::ErrorDetail::NotEnoughVotingPowerSigned(e) => {
+                        assert_eq!((e.got, e.needed), (20, 26));
+                    },
+                    _ => panic!("unexpected error: {err}"),
+                }
+                assert!(set
+                    .verify_commit_light(&verifier, &chain_id, &block_id(), commit.height, &commit)
+                    .is_err());
+                // A third of the voting power is enough to be trusted.
+                set.verify_commit_light_trusting(&verifier, &chain_id, &commit, Default::default())
+                    .unwrap();
+            }
+
+            #[test]
+            fn invalid_signature() {
+                let fixture = Fixture::new(4);
+                let set = &fixture.validator_set;
+                let mut commit = make_commit(&fixture, &[Some(true); 4]);
+                tamper(&mut commit, 3);
+                let chain_id = CHAIN_ID.parse().unwrap();
+                let verifier = signature::Verifier;
+
+                let err = set
+                    .verify_commit(&verifier, &chain_id, &block_id(), commit.height, &commit)
+                    .unwrap_err();
+                match err.detail() {
+                    crate::error::ErrorDetail::InvalidCommitSignature(e) => {
+                        assert_eq!(e.index, 3);
+                        assert_eq!(e.validator, set.validators()[3].address);
+                    },
+                    _ => panic!("unexpected error: {err}"),
+                }
+                // Light verification stops before the invalid signature.
+                set.verify_commit_light(&verifier, &chain_id, &block_id(), commit.height, &commit)
+                    .unwrap();
+
+                // Signatures of nil votes are only checked by full verification.
+                let mut commit =
+                    make_commit(&fixture, &[Some(true), Some(true), Some(true), Some(false)]);
+                tamper(&mut commit, 3);
+                assert!(set
+                    .verify_commit(&verifier, &chain_id, &block_id(), commit.height, &commit)
+                    .is_err());
+                set.verify_commit_light(&verifier, &chain_id, &block_id(), commit.height, &commit)
+                    .unwrap();
+            }
+
+            #[test]
+            fn commit_mismatch() {
+                let fixture = Fixture::new(4);
+                let set = &fixture.validator_set;
+                let commit = make_commit(&fixture, &[Some(true); 4]);
+                let chain_id = CHAIN_ID.parse().unwrap();
+                let verifier = signature::Verifier;
+
+                let other_height = commit.height.increment();
+                assert!(set
+                    .verify_commit(&verifier, &chain_id, &block_id(), other_height, &commit)
+                    .is_err());
+                assert!(set
+                    .verify_commit(
+                        &verifier,
+                        &chain_id,
+                        &Default::default(),
+                        commit.height,
+                        &commit
+                    )
+                    .is_err());
+                let other_set = Fixture::from_keys(fixture.keys[..3].to_vec()).validator_set;
+                assert!(other_set
+                    .verify_commit(&verifier, &chain_id, &block_id(), commit.height, &commit)
+                    .is_err());
+                let other_chain_id = "other-chain".parse().unwrap();
+                assert!(set
+                    .verify_commit(
+                        &verifier,
+                        &other_chain_id,
+                        &block_id(),
+                        commit.height,
+                        &commit
+                    )
+                    .is_err());
+            }
+
+            #[test]
+            fn light_trusting_other_set() {
+                let keys = signing_keys(6);
+                let fixture = Fixture::from_keys(keys[..4].to_vec());
+                let set = &fixture.validator_set;
+                let commit = make_commit(&fixture, &[Some(true); 4]);
+                let chain_id = CHAIN_ID.parse().unwrap();
+                let verifier = signature::Verifier;
+
+                // Two of the four validators of the trusted set signed.
+                let trusted = Fixture::from_keys(vec![
+                    keys[0].clone(),
+                    keys[1].clone(),
+                    keys[4].clone(),
+                    keys[5].clone(),
+                ])
+                .validator_set;
+                let overlap = trusted
+                    .validators()
+                    .iter()
+                    .filter(|v| set.validator(v.address).is_some())
+                    .count();
+                assert_eq!(overlap, 2);
+                trusted
+                    .verify_commit_light_trusting(&verifier, &chain_id, &commit, Default::default())
+                    .unwrap();
+                let two_thirds = TrustThresholdFraction::TWO_THIRDS;
+                assert!(trusted
+                    .verify_commit_light_trusting(&verifier, &chain_id, &commit, two_thirds)
+                    .is_err());
+
+                // The same validator cannot vote twice.
+                let mut commit = commit;
+                commit.signatures[1] = commit.signatures[0].clone();
+                let err = set
+                    .verify_commit_light_trusting(&verifier, &chain_id, &commit, two_thirds)
+                    .unwrap_err();
+                assert!(matches!(
+                    err.detail(),
+                    crate::error::ErrorDetail::DuplicateValidatorVote(_)
+                ));
+            }
+        }
+
+        #[cfg(feature = "bls12-381")]
+        #[test]
+        fn bls12_381_hash_bytes() {
+            let pk = PublicKey::from_raw_bls12_381(&[0xab; 48]).unwrap();
+            let validator = Info::new(pk, vote::Power::from(10_u32));
+
+            // SimpleValidator { pub_key: PublicKey { bls12381: [0xab; 48] }, voting_power: 10 }
+            let mut expected = vec![0x0a, 0x32, 0x1a, 0x30];
+            expected.extend([0xab; 48]);
+            expected.extend([0x10, 0x0a]);
+            assert_eq!(validator.hash_bytes(), expected);
+        }
+    }
+
+    mod proposer_priority {
+        use super::*;
+
+        fn make_validator(id: u8, power: u64) -> Info {
+            let pub_key = PublicKey::from_raw_ed25519(&[id; 32]).unwrap();
+            Info {
+                address: account::Id::new([id; 20]),
+                pub_key,
+                power: power.try_into().unwrap(),
+                name: None,
+                proposer_priority: ProposerPriority::default(),
+            }
+        }
+
+        /// A validator set as created by CometBFT from a list of validators.
+        fn make_set(powers: &[u64]) -> Set {
+            let validators = powers
+                .iter()
+                .zip(0..)
+                .map(|(&power, id)| make_validator(id, power))
+                .collect::<Vec<_>>();
+            let mut set = Set::without_proposer(vec![]);
+            set.update_with_change_set(&validators).unwrap();
+            set.increment_proposer_priority(1);
+            set
+        }
+
+        #[cfg(feature = "rust-crypto")]
+        #[test]
+        fn json_matches_cometbft() {
+            let validators = [10u32, 20]
+                .into_iter()
+                .zip(1..)
+                .map(|(power, id)| {
+                    Info::new(
+                        PublicKey::from_raw_ed25519(&[id; 32]).unwrap(),
+                        power.into(),
+                    )
+                })
+                .collect();
+            let mut set = Set::new(validators, None);
+            set.increment_proposer_priority(1);
+            let json = serde_json::to_value(&set).unwrap();
+            assert!(json.get("total_voting_power").is_none());
+            let validator = &json["validators"][0];
+            assert_eq!(validator["voting_power"], "20");
+            assert_eq!(
+                validator["proposer_priority"],
+                set.validators()[0].proposer_priority.value().to_string()
+            );
+            assert!(validator.get("power").is_none());
+            assert!(validator.get("name").is_none());
+
+            // Unlike the genesis form, this keeps the proposer priorities.
+            let parsed: Set = serde_json::from_value(json).unwrap();
+            assert_eq!(parsed, set);
+        }
+
+        fn proposer_id(set: &Set) -> u8 {
+            set.proposer().as_ref().unwrap().address.as_bytes()[0]
+        }
+
+        fn priorities(set: &Set) -> Vec<i64> {
+            set.validators()
+                .iter()
+                .map(|v| v.proposer_priority.value())
+                .collect()
+        }
+
+        #[test]
+        fn equal_power_round_robin() {
+            let mut set = make_set(&[100, 100, 100]);
+            for i in 0..15 {
+                assert_eq!(proposer_id(&set), i % 3);
+                set.increment_proposer_priority(1);
+            }
+        }
+
+        #[test]
+        fn higher_power_proposes_first() {
+            let mut set = make_set(&[100, 100, 400]);
+            assert_eq!(proposer_id(&set), 2);
+            set.increment_proposer_priority(1);
+            assert_eq!(proposer_id(&set), 0);
+
+            let mut set = make_set(&[100, 100, 401]);
+            assert_eq!(proposer_id(&set), 2);
+            set.increment_proposer_priority(1);
+            assert_eq!(proposer_id(&set), 2);
+        }
+
+        #[test]
+        fn proposer_frequency_is_proportional_to_power() {
+            let mut set = make_set(&[4, 5, 3]);
+            let mut counts = [0; 3];
+            for _ in 0..120 {
+                counts[proposer_id(&set) as usize] += 1;
+                set.increment_proposer_priority(1);
+            }
+            assert_eq!(counts, [40, 50, 30]);
+        }
+
+        #[test]
+        fn priorities_are_centered() {
+            let mut set = make_set(&[1, 2, 3]);
+            // The first round selects the validator with most power.
+            assert_eq!(priorities(&set), vec![-3, 2, 1]);
+            set.increment_proposer_priority(3);
+            assert!(priorities(&set).iter().sum::<i64>().abs() < 3);
+        }
+
+        #[test]
+        fn rescale_priorities() {
+            let mut set = make_set(&[1, 1]);
+            set.validators[0].proposer_priority = ProposerPriority::from(100);
+            set.validators[1].proposer_priority = ProposerPriority::from(-100);
+            set.increment_proposer_priority(1);
+            // The difference of 200 is scaled down by 50 to fit within 4, then
+            // the validator with the highest priority is selected.
+            assert_eq!(priorities(&set), vec![1, -1]);
+            assert_eq!(proposer_id(&set), 0);
+        }
+
+        #[test]
+        fn add_update_and_remove_validators() {
+            let mut set = make_set(&[10, 20]);
+
+            let changes = [
+                make_validator(0, 0),
+                make_validator(1, 30),
+                make_validator(2, 30),
+            ];
+            set.update_with_change_set(&changes).unwrap();
+
+            assert_eq!(set.total_voting_power().value(), 60);
+            let ids = set
+                .validators()
+                .iter()
+                .map(|v| v.address.as_bytes()[0])
+                .collect::<Vec<_>>();
+            assert_eq!(ids, vec![1, 2]);
+            // The new validator starts at -1.125 times the total power before
+            // removals (70), then the priorities are centered.
+            let existing = set.validators[0].proposer_priority.value();
+            let added = set.validators[1].proposer_priority.value();
+            assert_eq!(existing - added, 78 - 10);
+            assert!((existing + added).abs() <= 1);
+        }
+
+        #[test]
+        fn reject_invalid_changes() {
+            let mut set = make_set(&[10, 20]);
+            let original = set.clone();
+
+            // Duplicate entries.
+            let changes = [make_validator(2, 10), make_validator(2, 20)];
+            assert!(set.update_with_change_set(&changes).is_err());
+
+            // Removal of an unknown validator.
+            assert!(set.update_with_change_set(&[make_validator(2, 0)]).is_err());
+
+            // Removal of all validators.
+            let changes = [make_validator(0, 0), make_validator(1, 0)];
+            assert!(set.update_with_change_set(&changes).is_err());
+
+            // Total voting power overflow.
+            let changes = [
+                make_validator(0, Set::MAX_TOTAL_VOTING_POWER - 20),
+                make_validator(2, 1),
+            ];
+            assert!(set.update_with_change_set(&changes).is_err());
+
+            assert_eq!(set, original);
+        }
+
+        #[cfg(feature = "rust-crypto")]
+        #[test]
+        fn apply_updates() {
+            let mut set = make_set(&[]);
+            assert!(set.proposer().is_none());
+
+            let pub_key = PublicKey::from_raw_ed25519(&[7; 32]).unwrap();
+            let update = Update {
+                pub_key,
+                power: 10u32.into(),
+            };
+            set.apply_updates(&[update]).unwrap();
+            assert_eq!(set.validators().len(), 1);
+            assert_eq!(set.proposer().as_ref().unwrap().pub_key, pub_key);
+            assert_eq!(set.total_voting_power().value(), 10);
+        }
     }
 
     #[test]
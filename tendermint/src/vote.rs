@@ -4,6 +4,7 @@ mod canonical_vote;
 mod power;
 mod sign_vote;
 mod validator_index;
+mod vote_set;
 
 use core::{fmt, str::FromStr};
 
@@ -14,10 +15,11 @@ use tendermint_proto::{Error as ProtobufError, Protobuf};
 
 pub use self::{
     canonical_vote::CanonicalVote, power::Power, sign_vote::*, validator_index::ValidatorIndex,
+    vote_set::VoteSet,
 };
 use crate::{
-    account, block, chain::Id as ChainId, consensus::State, error::Error, hash, prelude::*,
-    Signature, Time,
+    account, block, chain::Id as ChainId, consensus::State, crypto::signature::Signer,
+    error::Error, hash, prelude::*, Signature, Time,
 };
 
 /// Votes are signed messages from validators for a particular block which
@@ -111,7 +113,8 @@ mod v0_34 {
                 r#type: value.vote_type.into(),
                 height: value.height.into(),
                 round: value.round.into(),
-                block_id: value.block_id.map(Into::into),
+                // The Go implementation encodes a nil block ID as an empty struct.
+                block_id: Some(value.block_id.unwrap_or_default().into()),
                 timestamp: value.timestamp.map(Into::into),
                 validator_address: value.validator_address.into(),
                 validator_index: value.validator_index.into(),
@@ -162,7 +165,8 @@ mod v0_37 {
                 r#type: value.vote_type.into(),
                 height: value.height.into(),
                 round: value.round.into(),
-                block_id: value.block_id.map(Into::into),
+                // The Go implementation encodes a nil block ID as an empty struct.
+                block_id: Some(value.block_id.unwrap_or_default().into()),
                 timestamp: value.timestamp.map(Into::into),
                 validator_address: value.validator_address.into(),
                 validator_index: value.validator_index.into(),
@@ -213,7 +217,8 @@ mod v0_38 {
                 r#type: value.vote_type.into(),
                 height: value.height.into(),
                 round: value.round.into(),
-                block_id: value.block_id.map(Into::into),
+                // The Go implementation encodes a nil block ID as an empty struct.
+                block_id: Some(value.block_id.unwrap_or_default().into()),
                 timestamp: value.timestamp.map(Into::into),
                 validator_address: value.validator_address.into(),
                 validator_index: value.validator_index.into(),
@@ -270,6 +275,20 @@ impl Vote {
         Protobuf::<RawCanonicalVote>::encode_length_delimited_vec(canonical)
     }
 
+    /// Sign the vote with the given signer and store the resulting signature
+    /// in the `signature` field.
+    ///
+    /// Only the canonical vote is signed; the vote extension, if any, is left untouched.
+    pub fn sign<S>(&mut self, chain_id: ChainId, signer: &S) -> Result<(), Error>
+    where
+        S: Signer + ?Sized,
+    {
+        let sign_bytes = self.clone().into_signable_vec(chain_id);
+        let signature = signer.sign(&sign_bytes).map_err(Error::signing)?;
+        self.signature = Some(signature);
+        Ok(())
+    }
+
     /// Consensus state from this vote - This doesn't seem to be used anywhere.
     #[deprecated(
         since = "0.17.0",
@@ -0,0 +1,511 @@
+//! Collection of the votes of a validator set for a given height, round and
+//! vote type.
+//!
+//! Based on <https://github.com/cometbft/cometbft/blob/v0.38.x/types/vote_set.go>
+
+use alloc::collections::BTreeMap;
+
+use super::{Type, ValidatorIndex, Vote};
+use crate::{
+    block::{self, Commit, CommitSig},
+    chain::Id as ChainId,
+    crypto::signature::Verifier,
+    error::Error,
+    prelude::*,
+    validator,
+};
+
+/// The votes of a validator set for a given height, round and vote type.
+///
+/// Votes are added with [`VoteSet::add_vote`], which verifies them against
+/// the validator set and tallies their voting power per block ID. A vote for
+/// nil is tallied under [`block::Id::default`].
+///
+/// A validator may only vote for one block. Once a block has received more
+/// than 2/3 of the voting power, its votes replace any conflicting votes from
+/// the same validators, so that a [`Commit`] can be made for it.
+///
+/// Unlike CometBFT, claims of 2/3 majorities by peers are not tracked.
+#[derive(Clone, Debug)]
+pub struct VoteSet {
+    chain_id: ChainId,
+    height: block::Height,
+    round: block::Round,
+    vote_type: Type,
+    validator_set: validator::Set,
+    /// The vote of each validator, by validator index.
+    votes: Vec<Option<Vote>>,
+    /// The voting power of the validators in `votes`.
+    sum: u64,
+    /// The first block to receive more than 2/3 of the voting power.
+    maj23: Option<block::Id>,
+    votes_by_block: BTreeMap<block::Id, BlockVotes>,
+}
+
+/// The votes for a single block.
+#[derive(Clone, Debug)]
+struct BlockVotes {
+    votes: Vec<Option<Vote>>,
+    sum: u64,
+}
+
+impl VoteSet {
+    /// Create an empty vote set.
+    pub fn new(
+        chain_id: ChainId,
+        height: block::Height,
+        round: block::Round,
+        vote_type: Type,
+        validator_set: validator::Set,
+    ) -> Self {
+        let size = validator_set.validators().len();
+        Self {
+            chain_id,
+            height,
+            round,
+            vote_type,
+            validator_set,
+            votes: vec![None; size],
+            sum: 0,
+            maj23: None,
+            votes_by_block: BTreeMap::new(),
+        }
+    }
+
+    /// Height of the votes.
+    pub fn height(&self) -> block::Height {
+        self.height
+    }
+
+    /// Round of the votes.
+    pub fn round(&self) -> block::Round {
+        self.round
+    }
+
+    /// Type of the votes.
+    pub fn vote_type(&self) -> Type {
+        self.vote_type
+    }
+
+    /// The validators whose votes are collected.
+    pub fn validator_set(&self) -> &validator::Set {
+        &self.validator_set
+    }
+
+    /// Add a vote, after checking that it is for the expected height, round
+    /// and type, and that it is correctly signed by the validator at its
+    /// index.
+    ///
+    /// Returns `Ok(false)` if the vote is already known. A vote for another
+    /// block than a previous vote from the same validator is rejected with
+    /// an [`ErrorDetail::ConflictingVotes`] error, which holds both votes and
+    /// can be turned into evidence with
+    /// [`DuplicateVoteEvidence::from_conflicting_votes`]. Such a vote is not
+    /// tallied, but replaces the previous vote of the validator if it is for
+    /// the 2/3 majority block.
+    ///
+    /// Vote extensions are not verified.
+    ///
+    /// [`ErrorDetail::ConflictingVotes`]: crate::error::ErrorDetail::ConflictingVotes
+    /// [`DuplicateVoteEvidence::from_conflicting_votes`]: crate::evidence::DuplicateVoteEvidence::from_conflicting_votes
+    pub fn add_vote<V>(&mut self, vote: Vote, verifier: &V) -> Result<bool, Error>
+    where
+        V: Verifier,
+    {
+        if (vote.height, vote.round, vote.vote_type) != (self.height, self.round, self.vote_type) {
+            return Err(Error::invalid_vote(format!(
+                "expected {}/{}/{}, but got {}/{}/{}",
+                self.height, self.round, self.vote_type, vote.height, vote.round, vote.vote_type
+            )));
+        }
+
+        let index = usize::from(vote.validator_index);
+        let validator = self.validator_set.validators().get(index).ok_or_else(|| {
+            Error::invalid_vote(format!(
+                "cannot find validator {} in a set of size {}",
+                index,
+                self.votes.len()
+            ))
+        })?;
+        if validator.address != vote.validator_address {
+            return Err(Error::invalid_vote(format!(
+                "expected validator {} at index {}, but got {}",
+                validator.address, index, vote.validator_address
+            )));
+        }
+
+        let block_key = vote.block_id.unwrap_or_default();
+        if let Some(existing) = self.vote_for_block(index, &block_key) {
+            if existing.signature == vote.signature {
+                return Ok(false);
+            }
+            return Err(Error::invalid_vote(format!(
+                "non-deterministic signature from validator {}",
+                vote.validator_address
+            )));
+        }
+
+        let signature = vote.signature.clone().ok_or_else(Error::empty_signature)?;
+        let sign_bytes = vote.clone().into_signable_vec(self.chain_id.clone());
+        validator.verify_signature(verifier, &sign_bytes, &signature)?;
+
+        let power = validator.power();
+        match self.add_verified_vote(vote.clone(), block_key, power) {
+            Some(conflicting) => Err(Error::conflicting_votes(
+                Box::new(conflicting),
+                Box::new(vote),
+            )),
+            None => Ok(true),
+        }
+    }
+
+    /// Get the known vote of the validator for the given block.
+    fn vote_for_block(&self, index: usize, block_key: &block::Id) -> Option<&Vote> {
+        self.votes[index]
+            .as_ref()
+            .filter(|vote| vote.block_id.unwrap_or_default() == *block_key)
+            .or_else(|| self.votes_by_block.get(block_key)?.votes[index].as_ref())
+    }
+
+    /// Add a verified vote, returning the previous vote of the validator if
+    /// it is for another block.
+    fn add_verified_vote(&mut self, vote: Vote, block_key: block::Id, power: u64) -> Option<Vote> {
+        let index = usize::from(vote.validator_index);
+
+        let conflicting = match &self.votes[index] {
+            Some(existing) => {
+                let existing = existing.clone();
+                // Replace the vote if it is for the 2/3 majority block.
+                if self.maj23 == Some(block_key) {
+                    self.votes[index] = Some(vote.clone());
+                }
+                Some(existing)
+            },
+            None => {
+                self.votes[index] = Some(vote.clone());
+                self.sum += power;
+                None
+            },
+        };
+
+        if conflicting.is_some() {
+            return conflicting;
+        }
+
+        let size = self.votes.len();
+        let block_votes = self
+            .votes_by_block
+            .entry(block_key)
+            .or_insert_with(|| BlockVotes {
+                votes: vec![None; size],
+                sum: 0,
+            });
+        let quorum = self.validator_set.total_voting_power().value() * 2 / 3 + 1;
+        let previous_sum = block_votes.sum;
+        block_votes.votes[index] = Some(vote);
+        block_votes.sum += power;
+
+        // Only the first block to cross the quorum is considered.
+        if previous_sum < quorum && quorum <= block_votes.sum && self.maj23.is_none() {
+            self.maj23 = Some(block_key);
+            for (index, vote) in block_votes.votes.iter().enumerate() {
+                if vote.is_some() {
+                    self.votes[index].clone_from(vote);
+                }
+            }
+        }
+
+        conflicting
+    }
+
+    /// Get the vote of the validator at the given index, if any.
+    ///
+    /// If the validator sent conflicting votes, this is the vote for the 2/3
+    /// majority block if there is one, or the first vote received otherwise.
+    pub fn vote(&self, validator_index: ValidatorIndex) -> Option<&Vote> {
+        self.votes.get(usize::from(validator_index))?.as_ref()
+    }
+
+    /// Get the votes, by validator index.
+    pub fn votes(&self) -> &[Option<Vote>] {
+        &self.votes
+    }
+
+    /// The voting power of the validators that voted.
+    pub fn voted_power(&self) -> u64 {
+        self.sum
+    }
+
+    /// The voting power of the validators that voted for the given block,
+    /// or for nil if the block ID is [`block::Id::default`].
+    pub fn block_power(&self, block_id: &block::Id) -> u64 {
+        self.votes_by_block
+            .get(block_id)
+            .map_or(0, |block_votes| block_votes.sum)
+    }
+
+    /// The block that received more than 2/3 of the voting power, if any.
+    ///
+    /// A 2/3 majority for nil is reported as [`block::Id::default`].
+    pub fn two_thirds_majority(&self) -> Option<block::Id> {
+        self.maj23
+    }
+
+    /// Whether a block (or nil) received more than 2/3 of the voting power.
+    pub fn has_two_thirds_majority(&self) -> bool {
+        self.maj23.is_some()
+    }
+
+    /// Whether more than 2/3 of the voting power voted, for any blocks.
+    pub fn has_two_thirds_any(&self) -> bool {
+        self.sum > self.validator_set.total_voting_power().value() * 2 / 3
+    }
+
+    /// Whether all the validators voted.
+    pub fn has_all(&self) -> bool {
+        self.sum == self.validator_set.total_voting_power().value()
+    }
+
+    /// Make a commit for the block that received more than 2/3 of the
+    /// precommits.
+    ///
+    /// The signatures are ordered as the validators. Votes for nil are
+    /// included, while votes for other blocks are marked as absent.
+    pub fn make_commit(&self) -> Result<Commit, Error> {
+        if self.vote_type != Type::Precommit {
+            return Err(Error::invalid_vote(format!(
+                "cannot make a commit from {} votes",
+                self.vote_type
+            )));
+        }
+        let block_id = self
+            .maj23
+            .filter(|block_id| *block_id != block::Id::default())
+            .ok_or_else(|| {
+                Error::invalid_vote(
+                    "cannot make a commit without a 2/3 majority for a block".into(),
+                )
+            })?;
+
+        let signatures = self
+            .votes
+            .iter()
+            .map(|vote| match vote {
+                Some(vote) if vote.block_id == Some(block_id) => CommitSig::BlockIdFlagCommit {
+                    validator_address: vote.validator_address,
+                    timestamp: vote.timestamp.unwrap_or_else(crate::Time::unix_epoch),
+                    signature: vote.signature.clone(),
+                },
+                Some(vote) if vote.block_id.is_none() => CommitSig::BlockIdFlagNil {
+                    validator_address: vote.validator_address,
+                    timestamp: vote.timestamp.unwrap_or_else(crate::Time::unix_epoch),
+                    signature: vote.signature.clone(),
+                },
+                _ => CommitSig::BlockIdFlagAbsent,
+            })
+            .collect();
+
+        Ok(Commit {
+            height: self.height,
+            round: self.round,
+            block_id,
+            signatures,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "rust-crypto"))]
+mod tests {
+    use super::*;
+    use crate::{
+        block::parts::Header as PartSetHeader,
+        crypto::default::signature,
+        error::ErrorDetail,
+        evidence::DuplicateVoteEvidence,
+        test::{Fixture, CHAIN_ID},
+        Hash, Time,
+    };
+
+    const HEIGHT: u32 = 2;
+
+    fn vote_set(fixture: &Fixture, vote_type: Type) -> VoteSet {
+        VoteSet::new(
+            CHAIN_ID.parse().unwrap(),
+            HEIGHT.into(),
+            0u16.into(),
+            vote_type,
+            fixture.validator_set.clone(),
+        )
+    }
+
+    fn block_id(n: u8) -> block::Id {
+        block::Id {
+            hash: Hash::Sha256([n; 32]),
+            part_set_header: PartSetHeader::new(1, Hash::Sha256([n + 1; 32])).unwrap(),
+        }
+    }
+
+    #[test]
+    fn two_thirds_majority_and_commit() {
+        let fixture = Fixture::new(4);
+        let mut votes = vote_set(&fixture, Type::Precommit);
+        let verifier = signature::Verifier;
+
+        assert!(votes
+            .add_vote(
+                fixture.vote(0, Type::Precommit, HEIGHT.into(), Some(block_id(1))),
+                &verifier
+            )
+            .unwrap());
+        assert!(votes
+            .add_vote(
+                fixture.vote(1, Type::Precommit, HEIGHT.into(), None),
+                &verifier
+            )
+            .unwrap());
+        assert!(votes
+            .add_vote(
+                fixture.vote(2, Type::Precommit, HEIGHT.into(), Some(block_id(1))),
+                &verifier
+            )
+            .unwrap());
+        assert!(!votes.has_two_thirds_majority());
+        assert!(votes.has_two_thirds_any());
+        assert!(votes.make_commit().is_err());
+
+        assert!(votes
+            .add_vote(
+                fixture.vote(3, Type::Precommit, HEIGHT.into(), Some(block_id(1))),
+                &verifier
+            )
+            .unwrap());
+        assert_eq!(votes.two_thirds_majority(), Some(block_id(1)));
+        assert_eq!(votes.block_power(&block_id(1)), 30);
+        assert_eq!(votes.block_power(&block::Id::default()), 10);
+        assert!(votes.has_all());
+
+        // Adding the same vote again is a no-op.
+        assert!(!votes
+            .add_vote(
+                fixture.vote(3, Type::Precommit, HEIGHT.into(), Some(block_id(1))),
+                &verifier
+            )
+            .unwrap());
+
+        let commit = votes.make_commit().unwrap();
+        assert_eq!(commit.block_id, block_id(1));
+        assert!(commit.signatures[0].is_commit());
+        assert!(commit.signatures[1].is_nil());
+        fixture
+            .validator_set
+            .verify_commit(
+                &verifier,
+                &CHAIN_ID.parse().unwrap(),
+                &block_id(1),
+                HEIGHT.into(),
+                &commit,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn no_commit_for_nil_or_prevotes() {
+        let fixture = Fixture::new(3);
+        let verifier = signature::Verifier;
+
+        let mut votes = vote_set(&fixture, Type::Precommit);
+        for index in 0..3 {
+            votes
+                .add_vote(
+                    fixture.vote(index, Type::Precommit, HEIGHT.into(), None),
+                    &verifier,
+                )
+                .unwrap();
+        }
+        assert_eq!(votes.two_thirds_majority(), Some(block::Id::default()));
+        assert!(votes.make_commit().is_err());
+
+        let mut votes = vote_set(&fixture, Type::Prevote);
+        for index in 0..3 {
+            votes
+                .add_vote(
+                    fixture.vote(index, Type::Prevote, HEIGHT.into(), Some(block_id(1))),
+                    &verifier,
+                )
+                .unwrap();
+        }
+        assert_eq!(votes.two_thirds_majority(), Some(block_id(1)));
+        assert!(votes.make_commit().is_err());
+    }
+
+    #[test]
+    fn reject_invalid_votes() {
+        let fixture = Fixture::new(3);
+        let mut votes = vote_set(&fixture, Type::Precommit);
+        let verifier = signature::Verifier;
+
+        // Wrong vote type.
+        let vote = fixture.vote(0, Type::Prevote, HEIGHT.into(), Some(block_id(1)));
+        assert!(votes.add_vote(vote, &verifier).is_err());
+
+        // Wrong validator index.
+        let mut vote = fixture.vote(0, Type::Precommit, HEIGHT.into(), Some(block_id(1)));
+        vote.validator_index = 1u32.try_into().unwrap();
+        assert!(votes.add_vote(vote, &verifier).is_err());
+
+        // Invalid signature.
+        let mut vote = fixture.vote(0, Type::Precommit, HEIGHT.into(), Some(block_id(1)));
+        vote.block_id = Some(block_id(2));
+        assert!(votes.add_vote(vote, &verifier).is_err());
+
+        assert_eq!(votes.voted_power(), 0);
+    }
+
+    #[test]
+    fn conflicting_votes() {
+        let fixture = Fixture::new(4);
+        let mut votes = vote_set(&fixture, Type::Precommit);
+        let verifier = signature::Verifier;
+
+        let first = fixture.vote(0, Type::Precommit, HEIGHT.into(), Some(block_id(3)));
+        votes.add_vote(first.clone(), &verifier).unwrap();
+        let second = fixture.vote(0, Type::Precommit, HEIGHT.into(), Some(block_id(1)));
+        let err = votes.add_vote(second.clone(), &verifier).unwrap_err();
+        let ErrorDetail::ConflictingVotes(e) = err.detail() else {
+            panic!("unexpected error: {err}");
+        };
+        assert_eq!(
+            (*e.vote_a.clone(), *e.vote_b.clone()),
+            (first.clone(), second.clone())
+        );
+        assert_eq!(votes.vote(0u32.try_into().unwrap()), Some(&first));
+        assert_eq!(votes.voted_power(), 10);
+
+        let evidence = DuplicateVoteEvidence::from_conflicting_votes(
+            *e.vote_a.clone(),
+            *e.vote_b.clone(),
+            Time::unix_epoch(),
+            &fixture.validator_set,
+        )
+        .unwrap();
+        assert_eq!(evidence.votes(), (&second, &first));
+        assert_eq!(evidence.validator_power.value(), 10);
+        assert_eq!(evidence.total_voting_power.value(), 40);
+
+        // Once block 1 has a 2/3 majority, the vote for it replaces the
+        // conflicting vote.
+        for index in 1..4 {
+            votes
+                .add_vote(
+                    fixture.vote(index, Type::Precommit, HEIGHT.into(), Some(block_id(1))),
+                    &verifier,
+                )
+                .unwrap();
+        }
+        assert_eq!(votes.two_thirds_majority(), Some(block_id(1)));
+        assert!(votes.add_vote(second.clone(), &verifier).is_err());
+        assert_eq!(votes.vote(0u32.try_into().unwrap()), Some(&second));
+        let commit = votes.make_commit().unwrap();
+        assert!(commit.signatures.iter().all(|sig| sig.is_commit()));
+    }
+}
@@ -137,8 +137,8 @@ impl Generator<vote::Vote> for Vote {
             extension_signature: None,
         };
 
-        let sign_bytes = get_vote_sign_bytes(block_header.chain_id, &vote);
-        vote.signature = Some(signing_key.sign(sign_bytes.as_slice()).into());
+        vote.sign(block_header.chain_id, &signing_key)
+            .map_err(|e| SimpleError::new(e.to_string()))?;
 
         Ok(vote)
     }
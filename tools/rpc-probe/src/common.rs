@@ -87,6 +87,16 @@ pub fn broadcast_tx(method: &str, key: &str, value: &str) -> PlannedInteraction
     .into()
 }
 
+pub fn check_tx(key: &str, value: &str) -> PlannedInteraction {
+    Request::new(
+        "check_tx",
+        json!({
+            "tx": encode_kvpair(key, value),
+        }),
+    )
+    .into()
+}
+
 pub fn commit(height: u64) -> PlannedInteraction {
     Request::new(
         "commit",
@@ -111,6 +121,10 @@ pub fn consensus_state() -> PlannedInteraction {
     Request::new("consensus_state", json!(null)).into()
 }
 
+pub fn dump_consensus_state() -> PlannedInteraction {
+    Request::new("dump_consensus_state", json!(null)).into()
+}
+
 pub fn genesis() -> PlannedInteraction {
     Request::new("genesis", json!(null)).into()
 }
@@ -119,6 +133,10 @@ pub fn net_info() -> PlannedInteraction {
     Request::new("net_info", json!(null)).into()
 }
 
+pub fn num_unconfirmed_txs() -> PlannedInteraction {
+    Request::new("num_unconfirmed_txs", json!(null)).into()
+}
+
 pub fn status() -> PlannedInteraction {
     Request::new("status", json!(null)).into()
 }
@@ -146,3 +164,13 @@ pub fn tx_search(
     )
     .into()
 }
+
+pub fn unconfirmed_txs(limit: u32) -> PlannedInteraction {
+    Request::new(
+        "unconfirmed_txs",
+        json!({
+            "limit": format!("{limit}"),
+        }),
+    )
+    .into()
+}
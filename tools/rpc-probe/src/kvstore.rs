@@ -34,9 +34,15 @@ pub fn quick_probe_plan(output_path: &Path, request_wait: Duration) -> Result<Pl
                 commit(10).with_name("commit_at_height_10"),
                 consensus_params(10),
                 consensus_state(),
+                // Peer states are only reported by a node with at least one
+                // peer, see the README.
+                dump_consensus_state(),
                 broadcast_tx("async", "async-key", "value"),
                 broadcast_tx("sync", "sync-key", "value"),
                 broadcast_tx("commit", "commit-key", "value"),
+                check_tx("check-key", "value"),
+                unconfirmed_txs(10),
+                num_unconfirmed_txs(),
                 genesis(),
                 net_info(),
                 status(),